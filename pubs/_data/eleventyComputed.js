@@ -1,11 +1,14 @@
 const fs = require('fs/promises');
 const path = require('node:path');
 
+// The event listed on pages that aren't for a specific year, e.g. the solutions list
+const DEFAULT_YEAR = 2023;
+
 function injectWriteUpUrl(day, posts) {
     return posts[day] ? {'Write Up': posts[day]} : {};
 }
 
-async function buildDay(file, day, posts) {
+async function buildDay(file, year, day, posts) {
     const contents = await fs.readFile(file, 'utf-8')
     const line = contents.split(/[\n\r]+/)[0]
     // This is my solution for [Advent of Code - Day 1: _Calorie
@@ -16,20 +19,20 @@ async function buildDay(file, day, posts) {
     const links = {
         Puzzle: puzzleURL,
         ...(injectWriteUpUrl(day, posts)),
-        Documentation: `/advent_of_code_2023/day_${day}/index.html`,
-        Source: `https://github.com/kamioftea/advent-of-code-2023/blob/main/src/day_${day}.rs`
+        Documentation: `/advent_of_code_2023/y${year}/day_${day}/index.html`,
+        Source: `https://github.com/kamioftea/advent-of-code-2023/blob/main/src/y${year}/day_${day}.rs`
     }
 
     return {day, title, links};
 }
 
-async function buildSolutionData(posts) {
+async function buildSolutionData(year, posts) {
     const solutions = [];
-    const dir = await fs.opendir(path.join('..', 'src'));
+    const dir = await fs.opendir(path.join('..', 'src', `y${year}`));
     for await (const entry of dir) {
         const matches = entry.name.match(/day_(\d+)\.rs/)
         if(entry.isFile() && matches) {
-            solutions.push(await buildDay(path.join(dir.path, entry.name), parseInt(matches[1]), posts))
+            solutions.push(await buildDay(path.join(dir.path, entry.name), year, parseInt(matches[1]), posts))
         }
     }
     return solutions
@@ -39,16 +42,19 @@ module.exports = function() {
     // noinspection JSUnusedGlobalSymbols
     return {
         solutions: async (data) => {
+            const year = data.year ?? DEFAULT_YEAR;
             const postsCollection = data.collections.post;
             const posts = Object.fromEntries(
-                [...(postsCollection ?? [])].map(post => [post.data.day, post.url])
+                [...(postsCollection ?? [])]
+                    .filter(post => post.data.year === year)
+                    .map(post => [post.data.day, post.url])
             );
-            return [...(await buildSolutionData(posts))].sort((a, b) => a.day - b.day)
+            return [...(await buildSolutionData(year, posts))].sort((a, b) => a.day - b.day)
         },
         title: data => data.title ||
             [
                 data.header,
-                'Advent of Code 2023',
+                `Advent of Code ${data.year ?? DEFAULT_YEAR}`,
                 'Jeff Horton'
             ].join(' | '),
         description: data => {
@@ -56,7 +62,7 @@ module.exports = function() {
                return data.description
             }
             if(data.day && data.header) {
-                return `A walkthrough of my solution for Advent of Code 2023 - ${data.header}`
+                return `A walkthrough of my solution for Advent of Code ${data.year ?? DEFAULT_YEAR} - ${data.header}`
             }
         }
     }
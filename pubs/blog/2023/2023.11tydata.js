@@ -0,0 +1,4 @@
+// 2023's posts were published before they were grouped by year, so keep their original URLs, e.g. /blog/day_1/
+module.exports = {
+    permalink: '/blog/{{ page.fileSlug }}/'
+};
@@ -0,0 +1,174 @@
+//! Stores answers that have been confirmed correct on the Advent of Code website, so that [`crate::runner::verify`]
+//! can check refactors haven't changed the results for the real puzzle inputs.
+//!
+//! Each year's answers are kept in [`answers_path`], one per line in the format `<day> <part> <answer>`. Blank lines
+//! and lines starting with `#` are ignored.
+
+use crate::solution::Answer;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Where confirmed answers for a year are recorded, alongside its puzzle inputs
+pub fn answers_path(year: u16) -> String {
+    format!("res/{}/answers.txt", year)
+}
+
+/// Confirmed answers keyed by `(day, part)`
+pub type RecordedAnswers = BTreeMap<(u8, u8), Answer>;
+
+/// A computed answer that differs from the recorded one
+#[derive(Eq, PartialEq, Debug)]
+pub struct Mismatch {
+    pub part: u8,
+    pub expected: Answer,
+    pub actual: Answer,
+}
+
+/// Load the recorded answers, treating a missing file as no answers having been recorded yet
+pub fn load_answers(path: &Path) -> io::Result<RecordedAnswers> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse_answers(&contents)
+            .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(RecordedAnswers::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Overwrite the answers file with the provided answers
+pub fn save_answers(path: &Path, answers: &RecordedAnswers) -> io::Result<()> {
+    fs::write(path, format_answers(answers))
+}
+
+/// Parse the contents of an answers file, see the [module documentation](self) for the format
+fn parse_answers(contents: &str) -> Result<RecordedAnswers, String> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            parse_answer_line(line).ok_or(format!(
+                "Invalid answer on line {}: '{}'",
+                line_number + 1,
+                line
+            ))
+        })
+        .collect()
+}
+
+/// Parse a line in the format `<day> <part> <answer>`. The answer is the rest of the line, so may contain spaces.
+fn parse_answer_line(line: &str) -> Option<((u8, u8), Answer)> {
+    let mut parts = line.splitn(3, ' ');
+    let day = parts.next()?.parse().ok()?;
+    let part = parts.next()?.parse().ok()?;
+    let answer = parts.next()?.parse().ok()?;
+
+    Some(((day, part), answer))
+}
+
+fn format_answers(answers: &RecordedAnswers) -> String {
+    answers
+        .iter()
+        .map(|((day, part), answer)| format!("{} {} {}\n", day, part, answer))
+        .collect()
+}
+
+/// Compare the answers calculated for a day against those that have been recorded. Parts without a recorded answer
+/// are ignored.
+pub fn find_mismatches(
+    answers: &RecordedAnswers,
+    day: u8,
+    actual: impl IntoIterator<Item = (u8, Answer)>,
+) -> Vec<Mismatch> {
+    actual
+        .into_iter()
+        .filter_map(|(part, actual)| {
+            answers
+                .get(&(day, part))
+                .filter(|&expected| *expected != actual)
+                .map(|expected| Mismatch {
+                    part,
+                    expected: expected.clone(),
+                    actual,
+                })
+        })
+        .collect()
+}
+
+/// The parts of a day that have a recorded answer
+pub fn recorded_parts(answers: &RecordedAnswers, day: u8) -> Vec<u8> {
+    answers
+        .keys()
+        .filter(|(recorded_day, _)| *recorded_day == day)
+        .map(|&(_, part)| part)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::*;
+
+    fn example_answers() -> RecordedAnswers {
+        vec![
+            ((1, 1), Answer::Integer(142)),
+            ((1, 2), Answer::Integer(281)),
+            ((7, 1), Answer::Text("ABC DEF".to_string())),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn can_parse_answers() {
+        let input = "\
+# Confirmed answers
+1 1 142
+1 2 281
+
+7 1 ABC DEF
+";
+        assert_eq!(parse_answers(input), Ok(example_answers()));
+        assert!(parse_answers("1 one 142").is_err());
+        assert!(parse_answers("1 1").is_err());
+    }
+
+    #[test]
+    fn can_format_answers() {
+        let formatted = format_answers(&example_answers());
+
+        assert_eq!(formatted, "1 1 142\n1 2 281\n7 1 ABC DEF\n");
+        assert_eq!(parse_answers(&formatted), Ok(example_answers()));
+    }
+
+    #[test]
+    fn can_find_mismatches() {
+        let answers = example_answers();
+
+        assert_eq!(
+            find_mismatches(
+                &answers,
+                1,
+                vec![(1, Answer::Integer(142)), (2, Answer::Integer(280))]
+            ),
+            vec![Mismatch {
+                part: 2,
+                expected: Answer::Integer(281),
+                actual: Answer::Integer(280)
+            }]
+        );
+        assert_eq!(
+            find_mismatches(&answers, 7, vec![(2, Answer::Integer(5))]),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn can_list_recorded_parts() {
+        let answers = example_answers();
+
+        assert_eq!(recorded_parts(&answers, 1), vec![1, 2]);
+        assert_eq!(recorded_parts(&answers, 7), vec![1]);
+        assert_eq!(recorded_parts(&answers, 9), Vec::<u8>::new());
+    }
+}
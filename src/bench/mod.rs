@@ -0,0 +1,511 @@
+//! Benchmarks the parsing and each part of a day's solution by running them repeatedly after a warm-up, and
+//! summarising the timings with [`Stats`].
+//!
+//! Results can be reported as text with [`format_text`], or as a table suitable for the README with
+//! [`format_markdown`].
+//!
+//! Results can also be saved as a [`Baseline`], and later runs compared against it with [`compare_to_baseline`] to
+//! flag any steps that have got slower. The baseline file has one step per line in the format
+//! `<day> <step> <min> <median> <mean> <std_dev>`, with timings stored in nanoseconds.
+
+use crate::error::Error;
+use crate::solution::DaySolution;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Where benchmark baselines for a year are saved unless another path is given
+pub fn default_baseline_path(year: u16) -> String {
+    format!("res/{}/bench-baseline.txt", year)
+}
+
+/// How many times to run each day, and what to do with the results
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct BenchOptions {
+    /// Runs that are discarded, to allow caches etc. to settle
+    pub warmup: usize,
+    /// Runs that are measured
+    pub iterations: usize,
+    /// Report as a markdown table rather than plain text
+    pub markdown: bool,
+    /// The file to save the results to, or compare them against, [`default_baseline_path`] if not given
+    pub baseline_path: Option<String>,
+    /// Save the results to the baseline file
+    pub save_baseline: bool,
+    /// Compare the results against the baseline file
+    pub compare: bool,
+    /// How much slower, as a percentage of the baseline median, a step can get before it is flagged
+    pub threshold_percent: u32,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 5,
+            iterations: 100,
+            markdown: false,
+            baseline_path: None,
+            save_baseline: false,
+            compare: false,
+            threshold_percent: 10,
+        }
+    }
+}
+
+/// The separately timed steps of running a day
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Ord, PartialOrd)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => write!(f, "Parse"),
+            Step::Part1 => write!(f, "Part 1"),
+            Step::Part2 => write!(f, "Part 2"),
+        }
+    }
+}
+
+impl Step {
+    /// The identifier used in the baseline file
+    fn key(&self) -> &'static str {
+        match self {
+            Step::Parse => "parse",
+            Step::Part1 => "part_1",
+            Step::Part2 => "part_2",
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Step::Parse),
+            "part_1" => Ok(Step::Part1),
+            "part_2" => Ok(Step::Part2),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Summary statistics for a set of timings
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+/// The timings for each step of a single day
+#[derive(Eq, PartialEq, Debug)]
+pub struct DayBenchmark {
+    pub day: u8,
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+}
+
+impl DayBenchmark {
+    /// Pair each [`Step`] with its [`Stats`]
+    pub fn steps(&self) -> [(Step, &Stats); 3] {
+        [
+            (Step::Parse, &self.parse),
+            (Step::Part1, &self.part_1),
+            (Step::Part2, &self.part_2),
+        ]
+    }
+}
+
+/// Previously saved benchmark results keyed by day and step
+pub type Baseline = BTreeMap<(u8, Step), Stats>;
+
+/// A step whose median time has changed compared to the baseline
+#[derive(Eq, PartialEq, Debug)]
+pub struct Comparison {
+    pub day: u8,
+    pub step: Step,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Whether the step has slowed by more than the threshold
+    pub regressed: bool,
+}
+
+impl Comparison {
+    /// The relative change in median time, e.g. `12.5` for 12.5% slower
+    pub fn change_percent(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+/// Run parsing and both parts once, returning how long each took. Fails if either part does, as the timing of a part
+/// that didn't produce an answer isn't meaningful.
+fn run_both_parts(
+    solution: &dyn DaySolution,
+    input: &str,
+) -> Result<(Duration, Duration, Duration), Error> {
+    let result = solution.run(input, &[1, 2])?;
+    let mut durations = Vec::with_capacity(2);
+    for part in result.parts {
+        part.answer?;
+        durations.push(part.duration);
+    }
+
+    Ok((result.parse_duration, durations[0], durations[1]))
+}
+
+/// Run the solution `options.warmup` times discarding the results, then `options.iterations` times recording how
+/// long parsing and each part took. Fails if the input can't be parsed or solved.
+pub fn bench_day(
+    day: u8,
+    solution: &dyn DaySolution,
+    input: &str,
+    options: &BenchOptions,
+) -> Result<DayBenchmark, Error> {
+    for _ in 0..options.warmup {
+        run_both_parts(solution, input)?;
+    }
+
+    let mut parse_samples = Vec::with_capacity(options.iterations);
+    let mut part_1_samples = Vec::with_capacity(options.iterations);
+    let mut part_2_samples = Vec::with_capacity(options.iterations);
+
+    for _ in 0..options.iterations {
+        let (parse, part_1, part_2) = run_both_parts(solution, input)?;
+        parse_samples.push(parse);
+        part_1_samples.push(part_1);
+        part_2_samples.push(part_2);
+    }
+
+    Ok(DayBenchmark {
+        day,
+        parse: calculate_stats(&mut parse_samples),
+        part_1: calculate_stats(&mut part_1_samples),
+        part_2: calculate_stats(&mut part_2_samples),
+    })
+}
+
+/// Summarise a list of timings. The samples are sorted in place to find the median.
+fn calculate_stats(samples: &mut [Duration]) -> Stats {
+    if samples.is_empty() {
+        return Stats {
+            min: Duration::ZERO,
+            median: Duration::ZERO,
+            mean: Duration::ZERO,
+            std_dev: Duration::ZERO,
+        };
+    }
+
+    samples.sort();
+
+    let count = samples.len() as f64;
+    let mid = samples.len() / 2;
+    let median = if samples.len().is_multiple_of(2) {
+        (samples[mid - 1] + samples[mid]) / 2
+    } else {
+        samples[mid]
+    };
+
+    let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+    let variance = samples
+        .iter()
+        .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+        .sum::<f64>()
+        / count;
+
+    Stats {
+        min: samples[0],
+        median,
+        mean: Duration::from_secs_f64(mean),
+        std_dev: Duration::from_secs_f64(variance.sqrt()),
+    }
+}
+
+/// Report the benchmarks as plain text, one line per step
+pub fn format_text(benchmarks: &[DayBenchmark]) -> String {
+    let mut output = String::new();
+
+    for benchmark in benchmarks {
+        output.push_str(&format!("==== Day {} ====\n", benchmark.day));
+        for (step, stats) in benchmark.steps() {
+            output.push_str(&format!(
+                "{:<7} min {:>10.2?}  median {:>10.2?}  mean {:>10.2?}  std dev {:>10.2?}\n",
+                format!("{}:", step),
+                stats.min,
+                stats.median,
+                stats.mean,
+                stats.std_dev
+            ));
+        }
+    }
+
+    output
+}
+
+/// Report the benchmarks as a markdown table, one row per step
+pub fn format_markdown(benchmarks: &[DayBenchmark]) -> String {
+    let mut output = "\
+| Day | Step | Min | Median | Mean | Std Dev |
+|----:|:-----|----:|-------:|-----:|--------:|
+"
+    .to_string();
+
+    for benchmark in benchmarks {
+        for (step, stats) in benchmark.steps() {
+            output.push_str(&format!(
+                "| {} | {} | {:.2?} | {:.2?} | {:.2?} | {:.2?} |\n",
+                benchmark.day, step, stats.min, stats.median, stats.mean, stats.std_dev
+            ));
+        }
+    }
+
+    output
+}
+
+/// Load a baseline, treating a missing file as an empty baseline
+pub fn load_baseline(path: &Path) -> io::Result<Baseline> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse_baseline(&contents)
+            .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Add the benchmarks to the baseline at `path`, replacing any previous results for the same days
+pub fn save_baseline(path: &Path, benchmarks: &[DayBenchmark]) -> io::Result<()> {
+    let mut baseline = load_baseline(path)?;
+    for benchmark in benchmarks {
+        for (step, stats) in benchmark.steps() {
+            baseline.insert((benchmark.day, step), *stats);
+        }
+    }
+
+    fs::write(path, format_baseline(&baseline))
+}
+
+/// Parse the contents of a baseline file, see the [module documentation](self) for the format
+fn parse_baseline(contents: &str) -> Result<Baseline, String> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_number, line)| {
+            parse_baseline_line(line).ok_or(format!(
+                "Invalid baseline on line {}: '{}'",
+                line_number + 1,
+                line
+            ))
+        })
+        .collect()
+}
+
+fn parse_baseline_line(line: &str) -> Option<((u8, Step), Stats)> {
+    let mut parts = line.split(' ');
+    let day = parts.next()?.parse().ok()?;
+    let step = parts.next()?.parse().ok()?;
+    let mut next_duration =
+        || -> Option<Duration> { parts.next()?.parse().ok().map(Duration::from_nanos) };
+
+    let stats = Stats {
+        min: next_duration()?,
+        median: next_duration()?,
+        mean: next_duration()?,
+        std_dev: next_duration()?,
+    };
+
+    Some(((day, step), stats))
+}
+
+fn format_baseline(baseline: &Baseline) -> String {
+    baseline
+        .iter()
+        .map(|((day, step), stats)| {
+            format!(
+                "{} {} {} {} {} {}\n",
+                day,
+                step.key(),
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.std_dev.as_nanos()
+            )
+        })
+        .collect()
+}
+
+/// Compare the median of each step against the baseline. Steps missing from the baseline are ignored.
+pub fn compare_to_baseline(
+    benchmarks: &[DayBenchmark],
+    baseline: &Baseline,
+    threshold_percent: u32,
+) -> Vec<Comparison> {
+    benchmarks
+        .iter()
+        .flat_map(|benchmark| {
+            benchmark.steps().into_iter().filter_map(|(step, stats)| {
+                baseline.get(&(benchmark.day, step)).map(|previous| {
+                    let threshold = 1.0 + threshold_percent as f64 / 100.0;
+
+                    Comparison {
+                        day: benchmark.day,
+                        step,
+                        baseline: previous.median,
+                        current: stats.median,
+                        regressed: stats.median.as_secs_f64()
+                            > previous.median.as_secs_f64() * threshold,
+                    }
+                })
+            })
+        })
+        .collect()
+}
+
+/// Report each comparison as a line of text, flagging those that have regressed
+pub fn format_comparisons(comparisons: &[Comparison]) -> String {
+    comparisons
+        .iter()
+        .map(|comparison| {
+            format!(
+                "Day {} {:<7} {:>10.2?} -> {:>10.2?} ({:+.1}%){}\n",
+                comparison.day,
+                format!("{}:", comparison.step),
+                comparison.baseline,
+                comparison.current,
+                comparison.change_percent(),
+                if comparison.regressed { " SLOWER" } else { "" }
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_micros(v)).collect()
+    }
+
+    fn example_stats() -> Stats {
+        Stats {
+            min: Duration::from_micros(2),
+            median: Duration::from_micros(4),
+            mean: Duration::from_micros(5),
+            std_dev: Duration::from_micros(2),
+        }
+    }
+
+    #[test]
+    fn can_calculate_stats() {
+        assert_eq!(
+            calculate_stats(&mut micros(&[4, 9, 2, 4, 5, 5, 4, 7])),
+            Stats {
+                min: Duration::from_micros(2),
+                median: Duration::from_micros(4) + Duration::from_nanos(500),
+                mean: Duration::from_micros(5),
+                std_dev: Duration::from_micros(2),
+            }
+        );
+
+        assert_eq!(
+            calculate_stats(&mut micros(&[3, 1, 2])).median,
+            Duration::from_micros(2)
+        );
+    }
+
+    fn example_benchmark(day: u8, part_2_median: u64) -> DayBenchmark {
+        DayBenchmark {
+            day,
+            parse: example_stats(),
+            part_1: example_stats(),
+            part_2: Stats {
+                median: Duration::from_micros(part_2_median),
+                ..example_stats()
+            },
+        }
+    }
+
+    #[test]
+    fn can_round_trip_baseline() {
+        let mut baseline = Baseline::new();
+        for (step, stats) in example_benchmark(7, 10).steps() {
+            baseline.insert((7, step), *stats);
+        }
+
+        let formatted = format_baseline(&baseline);
+
+        assert_eq!(
+            formatted,
+            "\
+7 parse 2000 4000 5000 2000
+7 part_1 2000 4000 5000 2000
+7 part_2 2000 10000 5000 2000
+"
+        );
+        assert_eq!(parse_baseline(&formatted), Ok(baseline));
+        assert!(parse_baseline("7 part_3 1 2 3 4").is_err());
+        assert!(parse_baseline("7 parse 1 2 3").is_err());
+    }
+
+    #[test]
+    fn can_compare_to_baseline() {
+        let mut baseline = Baseline::new();
+        for (step, stats) in example_benchmark(3, 10).steps() {
+            baseline.insert((3, step), *stats);
+        }
+
+        let comparisons = compare_to_baseline(
+            &[example_benchmark(3, 12), example_benchmark(4, 12)],
+            &baseline,
+            10,
+        );
+
+        assert_eq!(
+            comparisons
+                .iter()
+                .map(|c| (c.day, c.step, c.regressed))
+                .collect::<Vec<_>>(),
+            vec![
+                (3, Step::Parse, false),
+                (3, Step::Part1, false),
+                (3, Step::Part2, true)
+            ]
+        );
+        assert_eq!(comparisons[2].change_percent().round(), 20.0);
+
+        let within_threshold = compare_to_baseline(&[example_benchmark(3, 11)], &baseline, 10);
+        assert!(within_threshold.iter().all(|c| !c.regressed));
+    }
+
+    #[test]
+    fn can_format_markdown() {
+        let benchmarks = vec![DayBenchmark {
+            day: 3,
+            parse: example_stats(),
+            part_1: example_stats(),
+            part_2: example_stats(),
+        }];
+
+        assert_eq!(
+            format_markdown(&benchmarks),
+            "\
+| Day | Step | Min | Median | Mean | Std Dev |
+|----:|:-----|----:|-------:|-----:|--------:|
+| 3 | Parse | 2.00µs | 4.00µs | 5.00µs | 2.00µs |
+| 3 | Part 1 | 2.00µs | 4.00µs | 5.00µs | 2.00µs |
+| 3 | Part 2 | 2.00µs | 4.00µs | 5.00µs | 2.00µs |
+"
+        );
+    }
+}
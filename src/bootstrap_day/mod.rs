@@ -1,89 +1,721 @@
-use error_chain::error_chain;
-use reqwest::cookie::Jar;
-use reqwest::Url;
+//! Sets up a new day: downloads the puzzle input, generates the skeleton of the solution and its blog post, and
+//! registers the new solution so it can be run straight away.
+//!
+//! The solution and blog post are rendered from the templates in `templates/`, see [`crate::template`] for the
+//! placeholders they can use. If a template has been removed, the default version built into the tool is used.
+//!
+//! Nothing is written until the input has been fetched and every file rendered. If writing then fails part way
+//! through, the files already written are rolled back, so a failed bootstrap leaves the project as it was. A dry run
+//! reports what would be written instead, with a diff for each existing file that would change. It doesn't contact the
+//! website, so only includes the input if it has already been cached, and leaves the title and examples blank.
+//!
+//! Each year's event has its own module, e.g. `src/y2023/mod.rs`, which registers that year's days. Bootstrapping the
+//! first day of a new year creates its module, and registers it in `src/lib.rs`.
+
+use crate::client::AocClient;
+use crate::diff::unified_diff;
+use crate::error::{Result, ResultExt};
+use crate::puzzle::{parse_examples, parse_title};
+use crate::runner::default_input_path;
+use crate::template::{render, Example, Placeholders};
+use regex::Regex;
 use std::fs;
-use std::fs::File;
-use std::io::copy;
-use std::sync::Arc;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The crate root that declares each year's module and registers its solutions
+const LIB_PATH: &str = "src/lib.rs";
+
+/// The template for a day's solution. Its first line is read by `pubs/_data/eleventyComputed.js` to link to the
+/// solution, so should keep the same format.
+const DAY_TEMPLATE_PATH: &str = "templates/day.rs.tmpl";
+const DEFAULT_DAY_TEMPLATE: &str = include_str!("../../templates/day.rs.tmpl");
+
+/// The template for a day's blog post
+const POST_TEMPLATE_PATH: &str = "templates/post.md.tmpl";
+const DEFAULT_POST_TEMPLATE: &str = include_str!("../../templates/post.md.tmpl");
 
-error_chain! {
-     foreign_links {
-         Io(std::io::Error);
-         HttpRequest(reqwest::Error);
-     }
+/// Whether to overwrite files that already exist when bootstrapping a day, and whether to actually write them
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct BootstrapOptions {
+    /// Overwrite existing files, rather than skipping them
+    pub force: bool,
+    /// Report the files that would be written, rather than writing them
+    pub dry_run: bool,
 }
 
-pub fn bootstrap_day(day: u8) -> Result<()> {
-    let session_cookie =
-        fs::read_to_string("res/session_cookie.txt").expect("Failed to read session cookie");
+/// A file to write when bootstrapping a day
+struct PlannedFile {
+    filename: String,
+    contents: String,
+    /// Reported once the file has been written
+    summary: String,
+}
 
-    let cookie = format!("session={}; Domain=adventofcode.com", session_cookie);
-    let url = "https://www.adventofcode.com".parse::<Url>().unwrap();
+/// A change made to the project, recorded so that it can be undone
+enum Change {
+    CreatedDirectory(PathBuf),
+    /// A file that was written, along with its previous contents if it already existed
+    WroteFile {
+        path: PathBuf,
+        previous: Option<String>,
+    },
+}
 
-    let jar = Jar::default();
-    jar.add_cookie_str(cookie.as_str(), &url);
+/// Bootstrap `day` of `year`'s event in the project at `root`, fetching the input with `client`. Existing files are
+/// skipped unless [`BootstrapOptions::force`] is set, so that a finished solution is never replaced with the template.
+pub fn bootstrap_day(
+    client: &dyn AocClient,
+    root: &Path,
+    year: u16,
+    day: u8,
+    options: &BootstrapOptions,
+) -> Result<()> {
+    let files = plan_files(client, root, year, day, options)?;
 
-    let client = reqwest::blocking::Client::builder()
-        .cookie_store(true)
-        .cookie_provider(Arc::new(jar))
-        .build()?;
+    if options.dry_run {
+        for file in &files {
+            print!(
+                "{}",
+                describe_change(file, read_file(root, &file.filename)?.as_deref())
+            );
+        }
+        return Ok(());
+    }
 
-    let target = format!("https://www.adventofcode.com/2023/day/{}/input", day);
+    write_all(root, &files)?;
+    for file in &files {
+        println!("{}", file.summary);
+    }
 
-    let input_file_contents = client.get(target).send()?.text()?;
+    Ok(())
+}
 
-    let output_filename = format!("res/day-{}-input.txt", day);
-    let mut output_file = File::create(output_filename.clone())?;
-    copy(&mut input_file_contents.as_bytes(), &mut output_file)?;
+/// Fetch and render everything needed to bootstrap a day, returning the files to write. A dry run makes no requests,
+/// only using an input that is already cached, and leaving the title and examples as placeholders.
+fn plan_files(
+    client: &dyn AocClient,
+    root: &Path,
+    year: u16,
+    day: u8,
+    options: &BootstrapOptions,
+) -> Result<Vec<PlannedFile>> {
+    let mut files = Vec::new();
 
-    println!("Puzzle input saved to {}", output_filename);
+    let input_filename = default_input_path(year, day);
+    if should_write(root, &input_filename, options) {
+        let input = if options.dry_run {
+            client.cached_input(year, day)?
+        } else {
+            Some(
+                client
+                    .fetch_input(year, day)
+                    .chain_err(|| format!("Failed to fetch the input for day {}", day))?,
+            )
+        };
 
-    let rust_filename = format!("src/day_{}.rs", day);
-    let rust_contents = format!("\
-//! This is my solution for [Advent of Code - Day {day}: _???_](https://adventofcode.com/2023/day/{day})
-//!
-//!
+        match input {
+            Some(input) => files.push(PlannedFile {
+                summary: format!("Puzzle input saved to {}", input_filename),
+                filename: input_filename,
+                contents: input,
+            }),
+            None => println!(
+                "Would fetch the input for day {} and save it to {}",
+                day, input_filename
+            ),
+        }
+    }
 
-use std::fs;
+    let rust_filename = format!("src/y{}/day_{}.rs", year, day);
+    let markdown_filename = format!("pubs/blog/{}/day_{}.md", year, day);
+    let write_rust = should_write(root, &rust_filename, options);
+    let write_markdown = should_write(root, &markdown_filename, options);
 
-/// The entry point for running the solutions with the 'real' puzzle input.
-///
-/// - The puzzle input is expected to be at `<project_root>/res/day-{day}-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day {day}.
-pub fn run() {{
-    let _contents = fs::read_to_string(\"res/day-{day}-input.txt\").expect(\"Failed to read file\");
-}}
+    if write_rust || write_markdown {
+        let page = if options.dry_run {
+            println!(
+                "Would fetch the puzzle page for day {} to fill in its title and examples",
+                day
+            );
+            None
+        } else {
+            fetch_puzzle(client, year, day)
+        };
+        let placeholders = Placeholders {
+            year,
+            day,
+            title: page
+                .as_deref()
+                .and_then(parse_title)
+                .unwrap_or_else(|| "???".to_string()),
+            examples: page
+                .as_deref()
+                .map(parse_examples)
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+                .map(|(index, input)| Example {
+                    path: example_path(year, day, index + 1),
+                    input,
+                })
+                .collect(),
+        };
 
-#[cfg(test)]
-mod tests {{
+        if write_rust {
+            let solution =
+                render_template(root, DAY_TEMPLATE_PATH, DEFAULT_DAY_TEMPLATE, &placeholders)?;
+
+            for example in &placeholders.examples {
+                if should_write(root, &example.path, options) {
+                    files.push(PlannedFile {
+                        filename: example.path.clone(),
+                        contents: example.input.clone(),
+                        summary: format!("Example saved to {}", example.path),
+                    });
+                }
+            }
+
+            files.push(PlannedFile {
+                summary: format!("Rust file written {}", rust_filename),
+                filename: rust_filename,
+                contents: solution,
+            });
+        }
+
+        if write_markdown {
+            let placeholders = Placeholders {
+                title: escape_quotes(&placeholders.title),
+                ..placeholders
+            };
+            files.push(PlannedFile {
+                contents: render_template(
+                    root,
+                    POST_TEMPLATE_PATH,
+                    DEFAULT_POST_TEMPLATE,
+                    &placeholders,
+                )?,
+                summary: format!("Blog file written {}", markdown_filename),
+                filename: markdown_filename,
+            });
+        }
+    }
+
+    let module_filename = format!("src/y{}/mod.rs", year);
+    let module_source =
+        read_file(root, &module_filename)?.unwrap_or_else(|| year_module_template(year, day));
+    files.push(PlannedFile {
+        contents: register_day(&module_source, day)
+            .chain_err(|| format!("Failed to update {}", module_filename))?,
+        summary: format!("Day {} registered in {}", day, module_filename),
+        filename: module_filename,
+    });
 
-}}",
-        day=day
+    let lib_source =
+        read_file(root, LIB_PATH)?.ok_or_else(|| format!("{} is missing", LIB_PATH))?;
+    let lib_registered =
+        register_year(&lib_source, year).chain_err(|| format!("Failed to update {}", LIB_PATH))?;
+    if lib_registered != lib_source {
+        files.push(PlannedFile {
+            filename: LIB_PATH.to_string(),
+            contents: lib_registered,
+            summary: format!("{} registered in {}", year, LIB_PATH),
+        });
+    }
+
+    Ok(files)
+}
+
+/// The day's puzzle page, used for the title and examples. These are only a convenience, so if the page can't be
+/// fetched, bootstrapping continues with placeholders to be filled in by hand.
+fn fetch_puzzle(client: &dyn AocClient, year: u16, day: u8) -> Option<String> {
+    client
+        .fetch_puzzle(year, day)
+        .map_err(|error| {
+            eprintln!(
+                "Warning: failed to fetch the puzzle page for day {}: {}",
+                day,
+                error.describe()
+            )
+        })
+        .ok()
+}
+
+/// Where the `number`th example from a day's puzzle description is saved, counting from 1
+fn example_path(year: u16, day: u8, number: usize) -> String {
+    format!("res/{}/day-{}-example-{}.txt", year, day, number)
+}
+
+/// Render the template at `filename` within `root`, or `default` if there isn't one
+fn render_template(
+    root: &Path,
+    filename: &str,
+    default: &str,
+    placeholders: &Placeholders,
+) -> Result<String> {
+    let template = read_file(root, filename)?.unwrap_or_else(|| default.to_string());
+
+    Ok(render(&template, placeholders)
+        .map_err(|message| format!("Failed to render {}: {}", filename, message))?)
+}
+
+/// The title is single quoted in the blog post's front matter, where single quotes are escaped by doubling them
+fn escape_quotes(title: &str) -> String {
+    title.replace('\'', "''")
+}
+
+/// Whether `filename` can be written, either because it doesn't exist yet or overwriting is forced
+fn should_write(root: &Path, filename: &str, options: &BootstrapOptions) -> bool {
+    if options.force || !root.join(filename).exists() {
+        return true;
+    }
+
+    println!(
+        "Skipping {}, it already exists (use --force to overwrite)",
+        filename
     );
+    false
+}
+
+/// Read `filename` within `root`, or `None` if it doesn't exist yet
+fn read_file(root: &Path, filename: &str) -> Result<Option<String>> {
+    let path = root.join(filename);
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).chain_err(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// How writing `file` would change the project, given the existing contents of the file if there are any
+fn describe_change(file: &PlannedFile, existing: Option<&str>) -> String {
+    match existing {
+        None => format!("Would create {}\n", file.filename),
+        Some(existing) if existing == file.contents => {
+            format!("Would leave {} unchanged\n", file.filename)
+        }
+        Some(existing) => format!(
+            "Would overwrite {}\n{}",
+            file.filename,
+            unified_diff(&file.filename, existing, &file.contents)
+        ),
+    }
+}
 
-    let mut rust_file = File::create(rust_filename.clone())?;
-    copy(&mut rust_contents.as_bytes(), &mut rust_file)?;
+/// Write each of `files` within `root`, creating any missing directories. If any can't be written, the changes
+/// already made are rolled back.
+fn write_all(root: &Path, files: &[PlannedFile]) -> Result<()> {
+    let mut changes = Vec::new();
 
-    println!("Rust file written {}", rust_filename);
+    for file in files {
+        if let Err(error) = write_file(root, file, &mut changes) {
+            roll_back(changes);
+            return Err(error);
+        }
+    }
 
-    let markdown_filename = format!("pubs/blog/day_{}.md", day);
-    let markdown_contents = format!(
+    Ok(())
+}
+
+/// Write a single file, recording each change before it is made so that a partial write is also rolled back
+fn write_file(root: &Path, file: &PlannedFile, changes: &mut Vec<Change>) -> Result<()> {
+    let path = root.join(&file.filename);
+    let previous = read_file(root, &file.filename)?;
+
+    if let Some(dir) = path.parent() {
+        let mut missing: Vec<PathBuf> = dir
+            .ancestors()
+            .take_while(|ancestor| !ancestor.exists())
+            .map(Path::to_path_buf)
+            .collect();
+        missing.reverse();
+        changes.extend(missing.into_iter().map(Change::CreatedDirectory));
+
+        fs::create_dir_all(dir).chain_err(|| format!("Failed to create {}", dir.display()))?;
+    }
+
+    changes.push(Change::WroteFile {
+        path: path.clone(),
+        previous,
+    });
+    fs::write(&path, &file.contents).chain_err(|| format!("Failed to write {}", path.display()))
+}
+
+/// Undo `changes`, most recent first. A change that can't be undone is reported, and the rest are still attempted.
+fn roll_back(changes: Vec<Change>) {
+    for change in changes.into_iter().rev() {
+        let (path, result) = match change {
+            Change::CreatedDirectory(path) => {
+                let result = fs::remove_dir(&path);
+                (path, result)
+            }
+            Change::WroteFile {
+                path,
+                previous: Some(previous),
+            } => {
+                let result = fs::write(&path, previous);
+                (path, result)
+            }
+            Change::WroteFile {
+                path,
+                previous: None,
+            } => {
+                let result = fs::remove_file(&path);
+                (path, result)
+            }
+        };
+
+        match result {
+            Ok(()) => println!("Rolled back {}", path.display()),
+            Err(error) => eprintln!("Warning: failed to roll back {}: {}", path.display(), error),
+        }
+    }
+}
+
+/// The module for a year's event, created when its first day is bootstrapped, with that day already registered
+fn year_module_template(year: u16, day: u8) -> String {
+    format!(
         "\
----
-day: {day}
-tags: [post]
-header: 'Day {day}: ???'
----
+//! My solutions for [Advent of Code {year}](https://adventofcode.com/{year}). Each day is a public module, e.g.
+//! [`day_{day}`], and its [`crate::solution::Solution`] is registered in [`SOLUTIONS`].
+
+pub mod day_{day};
+
+use crate::solution::Registry;
+
+/// Each day's [`crate::solution::Solution`], keyed by day. Kept one per line so that bootstrapping can add to it.
+#[rustfmt::skip]
+pub static SOLUTIONS: &Registry = &[
+    ({day}, &day_{day}::Day{day}),
+];
 ",
+        year = year,
         day = day
+    )
+}
+
+/// Add the module declaration and `SOLUTIONS` entry for `day` to the source of a year's module, keeping both in day
+/// order. Anything already registered is left as is, so bootstrapping a day again is harmless.
+fn register_day(module_source: &str, day: u8) -> Result<String> {
+    let mut lines: Vec<String> = module_source.lines().map(String::from).collect();
+    expand_collapsed_array(&mut lines, "pub static SOLUTIONS: &Registry = &[");
+
+    insert_in_order(
+        &mut lines,
+        &Regex::new(r"^pub mod day_(\d+);$").unwrap(),
+        day.into(),
+        format!("pub mod day_{};", day),
+    )
+    .chain_err(|| "Failed to add the module declaration")?;
+
+    insert_in_order(
+        &mut lines,
+        &Regex::new(r"^    \((\d+), &day_\d+::Day\d+\),$").unwrap(),
+        day.into(),
+        format!("    ({}, &day_{}::Day{}),", day, day, day),
+    )
+    .chain_err(|| "Failed to add the solution to the registry")?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add the module declaration and [`crate::YEARS`] entry for `year` to the source of `lib.rs`, in the same way as
+/// [`register_day`]
+fn register_year(lib_source: &str, year: u16) -> Result<String> {
+    let mut lines: Vec<String> = lib_source.lines().map(String::from).collect();
+
+    insert_in_order(
+        &mut lines,
+        &Regex::new(r"^pub mod y(\d+);$").unwrap(),
+        year,
+        format!("pub mod y{};", year),
+    )
+    .chain_err(|| "Failed to add the module declaration")?;
+
+    insert_in_order(
+        &mut lines,
+        &Regex::new(r"^    \((\d+), y\d+::SOLUTIONS\),$").unwrap(),
+        year,
+        format!("    ({}, y{}::SOLUTIONS),", year, year),
+    )
+    .chain_err(|| "Failed to add the year's solutions")?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Put each entry of an array that `cargo fmt` has collapsed onto the line declaring it, e.g.
+/// `pub static SOLUTIONS: &Registry = &[(1, &day_1::Day1)];`, back on its own line so that it can be matched by
+/// [`insert_in_order`]. This happens to year modules created before they were marked `#[rustfmt::skip]`.
+fn expand_collapsed_array(lines: &mut Vec<String>, declaration: &str) {
+    let Some(index) = lines
+        .iter()
+        .position(|line| line.starts_with(declaration) && line.ends_with("];"))
+    else {
+        return;
+    };
+
+    let entries = lines[index][declaration.len()..lines[index].len() - 2].to_string();
+    let mut expanded = vec![declaration.to_string()];
+    expanded.extend(
+        Regex::new(r"\([^()]*\)")
+            .unwrap()
+            .find_iter(&entries)
+            .map(|entry| format!("    {},", entry.as_str())),
     );
+    expanded.push("];".to_string());
+
+    lines.splice(index..=index, expanded);
+}
 
-    let mut markdown_file = File::create(markdown_filename.clone())?;
-    copy(&mut markdown_contents.as_bytes(), &mut markdown_file)?;
+/// Insert `line` amongst the existing lines matching `pattern`, whose first capture is the day or year they are for
+fn insert_in_order(lines: &mut Vec<String>, pattern: &Regex, key: u16, line: String) -> Result<()> {
+    let existing: Vec<(usize, u16)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let captures = pattern.captures(line)?;
+            Some((index, captures[1].parse().ok()?))
+        })
+        .collect();
 
-    println!("Blog file written {}", markdown_filename);
+    if existing
+        .iter()
+        .any(|&(_, existing_key)| existing_key == key)
+    {
+        return Ok(());
+    }
 
-    // TODO - modify main.rs
+    let index = match existing
+        .iter()
+        .rev()
+        .find(|&&(_, existing_key)| existing_key < key)
+    {
+        Some(&(index, _)) => index + 1,
+        None => existing
+            .first()
+            .map(|&(index, _)| index)
+            .ok_or_else(|| format!("No lines like '{}' found", pattern))?,
+    };
 
+    lines.insert(index, line);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap_day::*;
+
+    const MODULE_SOURCE: &str = "\
+pub mod day_1;
+pub mod day_9;
+
+use crate::solution::Registry;
+
+pub static SOLUTIONS: &Registry = &[
+    (1, &day_1::Day1),
+    (9, &day_9::Day9),
+];
+";
+
+    #[test]
+    fn can_register_day() {
+        assert_eq!(
+            register_day(MODULE_SOURCE, 10).unwrap(),
+            "\
+pub mod day_1;
+pub mod day_9;
+pub mod day_10;
+
+use crate::solution::Registry;
+
+pub static SOLUTIONS: &Registry = &[
+    (1, &day_1::Day1),
+    (9, &day_9::Day9),
+    (10, &day_10::Day10),
+];
+"
+        );
+
+        let registered = register_day(MODULE_SOURCE, 5).unwrap();
+        assert!(registered.contains("pub mod day_1;\npub mod day_5;\npub mod day_9;"));
+        assert!(registered
+            .contains("(1, &day_1::Day1),\n    (5, &day_5::Day5),\n    (9, &day_9::Day9),"));
+    }
+
+    #[test]
+    fn can_register_day_again() {
+        let registered = register_day(MODULE_SOURCE, 10).unwrap();
+        assert_eq!(register_day(&registered, 10).unwrap(), registered);
+        assert_eq!(register_day(MODULE_SOURCE, 9).unwrap(), MODULE_SOURCE);
+    }
+
+    #[test]
+    fn can_register_year() {
+        let lib_source = "\
+pub mod solution;
+pub mod y2023;
+
+pub static YEARS: &Years = &[
+    (2023, y2023::SOLUTIONS),
+];
+";
+
+        assert_eq!(
+            register_year(lib_source, 2024).unwrap(),
+            "\
+pub mod solution;
+pub mod y2023;
+pub mod y2024;
+
+pub static YEARS: &Years = &[
+    (2023, y2023::SOLUTIONS),
+    (2024, y2024::SOLUTIONS),
+];
+"
+        );
+        assert_eq!(register_year(lib_source, 2023).unwrap(), lib_source);
+
+        let module = year_module_template(2024, 3);
+        assert!(module.contains("pub mod day_3;\n"));
+        assert_eq!(register_day(&module, 3).unwrap(), module);
+    }
+
+    fn placeholders(year: u16, day: u8, title: &str, example_count: usize) -> Placeholders {
+        Placeholders {
+            year,
+            day,
+            title: title.to_string(),
+            examples: (1..=example_count)
+                .map(|number| Example {
+                    path: example_path(year, day, number),
+                    input: String::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn can_fill_in_title() {
+        assert!(render(DEFAULT_DAY_TEMPLATE, &placeholders(2022, 1, "Calorie Counting", 0))
+            .unwrap()
+            .starts_with(
+            "//! This is my solution for [Advent of Code - Day 1: _Calorie Counting_](https://adventofcode.com/2022/day/1)\n"
+        ));
+        let post = render(
+            DEFAULT_POST_TEMPLATE,
+            &placeholders(2023, 3, "Gear Ratios", 0),
+        )
+        .unwrap();
+        assert!(post.contains("year: 2023\nday: 3\n"));
+        assert!(post.contains("header: 'Day 3: Gear Ratios'\n"));
+        assert_eq!(
+            escape_quotes("The Elves' Gear Ratios"),
+            "The Elves'' Gear Ratios"
+        );
+    }
+
+    #[test]
+    fn can_generate_example_tests() {
+        let render_tests = |example_count| {
+            let solution = render(
+                DEFAULT_DAY_TEMPLATE,
+                &placeholders(2023, 4, "Scratchcards", example_count),
+            )
+            .unwrap();
+            solution[solution.find("#[cfg(test)]").unwrap()..].to_string()
+        };
+
+        assert_eq!(render_tests(0), "#[cfg(test)]\nmod tests {\n}\n");
+        assert_eq!(
+            render_tests(2),
+            "\
+#[cfg(test)]
+mod tests {
+    use crate::y2023::day_4::*;
+    use std::fs;
+
+    /// The examples from the puzzle description, relative to the project root
+    const EXAMPLES: &[&str] = &[
+        \"res/2023/day-4-example-1.txt\",
+        \"res/2023/day-4-example-2.txt\",
+    ];
+
+    /// Load an example at runtime, as `res/` isn't committed. `None` if it hasn't been saved, e.g. in a fresh checkout.
+    fn read_example(path: &str) -> Option<String> {
+        fs::read_to_string(path).ok()
+    }
+
+    #[test]
+    fn can_parse_examples() {
+        for path in EXAMPLES {
+            if let Some(example) = read_example(path) {
+                Day4.parse(&example).unwrap();
+            }
+        }
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn can_describe_changes() {
+        let file = PlannedFile {
+            filename: "src/y2023/mod.rs".to_string(),
+            contents: "pub mod day_1;\npub mod day_2;\n".to_string(),
+            summary: String::new(),
+        };
+
+        assert_eq!(
+            describe_change(&file, None),
+            "Would create src/y2023/mod.rs\n"
+        );
+        assert_eq!(
+            describe_change(&file, Some("pub mod day_1;\npub mod day_2;\n")),
+            "Would leave src/y2023/mod.rs unchanged\n"
+        );
+        assert_eq!(
+            describe_change(&file, Some("pub mod day_1;\n")),
+            "\
+Would overwrite src/y2023/mod.rs
+--- a/src/y2023/mod.rs
++++ b/src/y2023/mod.rs
+@@ -1 +1,2 @@
+ pub mod day_1;
++pub mod day_2;
+"
+        );
+    }
+
+    #[test]
+    fn can_register_day_in_collapsed_registry() {
+        let collapsed = "\
+pub mod day_1;
+
+#[rustfmt::skip]
+pub static SOLUTIONS: &Registry = &[(1, &day_1::Day1)];
+";
+
+        assert_eq!(
+            register_day(collapsed, 2).unwrap(),
+            "\
+pub mod day_1;
+pub mod day_2;
+
+#[rustfmt::skip]
+pub static SOLUTIONS: &Registry = &[
+    (1, &day_1::Day1),
+    (2, &day_2::Day2),
+];
+"
+        );
+    }
+
+    #[test]
+    fn can_report_missing_registry() {
+        assert_eq!(
+            register_day("pub mod day_1;\n", 2).unwrap_err().describe(),
+            "Failed to add the solution to the registry\n  caused by: No lines like '^    \\((\\d+), &day_\\d+::Day\\d+\\),$' found"
+        );
+    }
+}
@@ -0,0 +1,626 @@
+//! Turns the command line arguments into a [`Command`] for the binary to act on.
+//!
+//! The supported forms are:
+//!
+//! ```text
+//! run <day> [--part 1|2] [--input <path>|-] [--input-text <text>] [--format text|json|jsonl]
+//! run --all [--parallel] [--format text|json|jsonl]
+//! batch <day> [--dir <path>] [--part 1|2] [--format text|json|jsonl]
+//! record <day> [--part 1|2]
+//! verify
+//! bench <day>|--all [--iterations <n>] [--warmup <n>] [--markdown]
+//!     [--save-baseline] [--compare] [--baseline <path>] [--threshold <percent>]
+//! bootstrap <day> [--force] [--dry-run]
+//! submit <day> <part>
+//! attempt <day> <part> correct|too-high|too-low|wrong <answer>
+//! ```
+//!
+//! Any command can also be given `--year <year>` to act on that year's event, see [`take_year`].
+//!
+//! When no arguments are given the binary falls back to prompting for a day interactively.
+
+use crate::bench::BenchOptions;
+use crate::bootstrap_day::BootstrapOptions;
+use crate::report::OutputFormat;
+use crate::runner::InputSource;
+use crate::solution::Answer;
+use crate::submit::Outcome;
+
+/// Usage text printed alongside any argument errors
+pub const USAGE: &str = "\
+Usage:
+    advent-of-code-2023                                    Prompt for a day to run
+    advent-of-code-2023 run <day> [--part 1|2] [--input <path>|-] [--input-text <text>]
+        [--format text|json|jsonl]
+                                                           Run a single day, reading the input from a file, stdin
+                                                           (-), or the text provided
+    advent-of-code-2023 run --all [--parallel] [--format text|json|jsonl]
+                                                           Run every day, optionally across several threads
+    advent-of-code-2023 batch <day> [--dir <path>] [--part 1|2] [--format text|json|jsonl]
+                                                           Run a day against every .txt file in a directory,
+                                                           res/<year>/day-<day>/ by default
+    advent-of-code-2023 record <day> [--part 1|2]          Record a day's answers as confirmed correct
+    advent-of-code-2023 verify                             Check every day still gives its recorded answers
+    advent-of-code-2023 bench <day>|--all [--iterations <n>] [--warmup <n>] [--markdown]
+        [--save-baseline] [--compare] [--baseline <path>] [--threshold <percent>]
+                                                           Time parsing and each part over many runs, optionally
+                                                           saving or comparing against a baseline
+    advent-of-code-2023 bootstrap <day> [--force] [--dry-run]
+                                                           Fetch the input and generate files for a new day,
+                                                           skipping any that exist unless forced, or show what
+                                                           would be written without writing it
+    advent-of-code-2023 submit <day> <part>                Submit an answer to the website, unless it has been
+                                                           submitted before
+    advent-of-code-2023 attempt <day> <part> correct|too-high|too-low|wrong <answer>
+                                                           Record an answer tried directly on the website
+    advent-of-code-2023 help                               Show this message
+
+Options:
+    --year <year>                                          The year of the event to use, by default the latest
+                                                           year with solutions";
+
+/// An action requested by the user
+#[derive(Eq, PartialEq, Debug)]
+pub enum Command {
+    /// Run a single day, optionally restricted to one part, and/or reading an alternative input
+    Run {
+        day: u8,
+        part: Option<u8>,
+        input: Option<InputSource>,
+        format: OutputFormat,
+    },
+    /// Run every day that has a solution, optionally running days concurrently
+    RunAll {
+        format: OutputFormat,
+        parallel: bool,
+    },
+    /// Run a day against each of the inputs in a directory, defaulting to [`crate::runner::default_batch_dir`]
+    Batch {
+        day: u8,
+        dir: Option<String>,
+        part: Option<u8>,
+        format: OutputFormat,
+    },
+    /// Run a day against its real input and store the answers as confirmed correct
+    Record { day: u8, part: Option<u8> },
+    /// Re-run every day, checking the answers match those recorded
+    Verify,
+    /// Repeatedly run a day, or all days if `day` is `None`, and report statistics on the timings
+    Bench {
+        day: Option<u8>,
+        options: BenchOptions,
+    },
+    /// Download the input, and generate the source and blog files for a new day
+    Bootstrap { day: u8, options: BootstrapOptions },
+    /// Run a day against its real input, and submit the answer for one part to the website
+    Submit { day: u8, part: u8 },
+    /// Add an answer tried directly on the website to the day's submissions ledger
+    Attempt {
+        day: u8,
+        part: u8,
+        outcome: Outcome,
+        answer: Answer,
+    },
+    /// Print the usage text
+    Help,
+}
+
+/// Parse the arguments (excluding the binary name) into a [`Command`]. Errors are returned as a message suitable to
+/// show the user alongside [`USAGE`].
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str);
+
+    match args.next() {
+        Some("run") => parse_run(args),
+        Some("batch") => parse_batch(args),
+        Some("record") => parse_record(args),
+        Some("verify") => {
+            expect_no_more_args(args)?;
+            Ok(Command::Verify)
+        }
+        Some("bench") => parse_bench(args),
+        Some("bootstrap") => parse_bootstrap(args),
+        Some("submit") => {
+            let day = parse_day(args.next())?;
+            let part = parse_part(args.next())?;
+            expect_no_more_args(args)?;
+            Ok(Command::Submit { day, part })
+        }
+        Some("attempt") => {
+            let day = parse_day(args.next())?;
+            let part = parse_part(args.next())?;
+            let outcome = args.next().ok_or("attempt requires an outcome")?.parse()?;
+            let answer = args.collect::<Vec<_>>().join(" ");
+            if answer.is_empty() {
+                return Err("attempt requires the answer that was tried".to_string());
+            }
+
+            Ok(Command::Attempt {
+                day,
+                part,
+                outcome,
+                answer: answer.parse().unwrap(),
+            })
+        }
+        Some("help") | Some("--help") | Some("-h") => Ok(Command::Help),
+        Some(other) => Err(format!("Unknown command '{}'", other)),
+        None => Err("No command given".to_string()),
+    }
+}
+
+/// Remove the `--year <year>` option, which can be given anywhere and applies to every command, from the arguments.
+/// Returns the year, if given, and the remaining arguments to pass to [`parse_args`].
+pub fn take_year(args: &[String]) -> Result<(Option<u16>, Vec<String>), String> {
+    let mut year = None;
+    let mut remaining = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = Some(parse_year(args.next().map(String::as_str))?),
+            _ => remaining.push(arg.to_string()),
+        }
+    }
+
+    Ok((year, remaining))
+}
+
+/// Parse the arguments following `run`. Flags and the day can be given in any order.
+fn parse_run<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut format = OutputFormat::default();
+    let mut all = false;
+    let mut parallel = false;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--all" => all = true,
+            "--parallel" => parallel = true,
+            "--part" => part = Some(parse_part(args.next())?),
+            "--input" => {
+                input = match args.next().ok_or("--input requires a path")? {
+                    "-" => Some(InputSource::Stdin),
+                    path => Some(InputSource::File(path.to_string())),
+                }
+            }
+            "--input-text" => {
+                let text = args.next().ok_or("--input-text requires the input")?;
+                input = Some(InputSource::Inline(text.to_string()))
+            }
+            "--format" => format = args.next().ok_or("--format requires a value")?.parse()?,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            _ if day.is_some() => return Err(format!("Unexpected argument '{}'", arg)),
+            _ => day = Some(parse_day(Some(arg))?),
+        }
+    }
+
+    match (day, all) {
+        (Some(_), false) if parallel => {
+            Err("--parallel can only be used when running all days".to_string())
+        }
+        (Some(day), false) => Ok(Command::Run {
+            day,
+            part,
+            input,
+            format,
+        }),
+        (None, true) if part.is_none() && input.is_none() => {
+            Ok(Command::RunAll { format, parallel })
+        }
+        (None, true) => {
+            Err("--part and --input can only be used when running a single day".to_string())
+        }
+        (Some(_), true) => Err("Specify either a day or --all, not both".to_string()),
+        (None, false) => Err("run requires a day or --all".to_string()),
+    }
+}
+
+/// Parse the arguments following `batch`
+fn parse_batch<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut day = None;
+    let mut dir = None;
+    let mut part = None;
+    let mut format = OutputFormat::default();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--dir" => dir = Some(args.next().ok_or("--dir requires a path")?.to_string()),
+            "--part" => part = Some(parse_part(args.next())?),
+            "--format" => format = args.next().ok_or("--format requires a value")?.parse()?,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            _ if day.is_some() => return Err(format!("Unexpected argument '{}'", arg)),
+            _ => day = Some(parse_day(Some(arg))?),
+        }
+    }
+
+    Ok(Command::Batch {
+        day: day.ok_or("batch requires a day")?,
+        dir,
+        part,
+        format,
+    })
+}
+
+/// Parse the arguments following `record`
+fn parse_record<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut day = None;
+    let mut part = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--part" => part = Some(parse_part(args.next())?),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            _ if day.is_some() => return Err(format!("Unexpected argument '{}'", arg)),
+            _ => day = Some(parse_day(Some(arg))?),
+        }
+    }
+
+    Ok(Command::Record {
+        day: day.ok_or("record requires a day")?,
+        part,
+    })
+}
+
+/// Parse the arguments following `bootstrap`
+fn parse_bootstrap<'a>(args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut day = None;
+    let mut options = BootstrapOptions::default();
+
+    for arg in args {
+        match arg {
+            "--force" => options.force = true,
+            "--dry-run" => options.dry_run = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            _ if day.is_some() => return Err(format!("Unexpected argument '{}'", arg)),
+            _ => day = Some(parse_day(Some(arg))?),
+        }
+    }
+
+    Ok(Command::Bootstrap {
+        day: day.ok_or("bootstrap requires a day")?,
+        options,
+    })
+}
+
+/// Parse the arguments following `bench`
+fn parse_bench<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut day = None;
+    let mut all = false;
+    let mut options = BenchOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--all" => all = true,
+            "--iterations" => options.iterations = parse_count(arg, args.next())?,
+            "--warmup" => {
+                options.warmup = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .ok_or("--warmup requires a number, which may be 0")?
+            }
+            "--markdown" => options.markdown = true,
+            "--save-baseline" => options.save_baseline = true,
+            "--compare" => options.compare = true,
+            "--baseline" => {
+                options.baseline_path =
+                    Some(args.next().ok_or("--baseline requires a path")?.to_string())
+            }
+            "--threshold" => {
+                options.threshold_percent = args
+                    .next()
+                    .and_then(|value| value.trim_end_matches('%').parse().ok())
+                    .ok_or("--threshold requires a percentage")?
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            _ if day.is_some() => return Err(format!("Unexpected argument '{}'", arg)),
+            _ => day = Some(parse_day(Some(arg))?),
+        }
+    }
+
+    match (day, all) {
+        (Some(_), true) => Err("Specify either a day or --all, not both".to_string()),
+        (None, false) => Err("bench requires a day or --all".to_string()),
+        (day, _) => Ok(Command::Bench { day, options }),
+    }
+}
+
+/// Parse the value of a flag that expects a positive number
+fn parse_count(flag: &str, arg: Option<&str>) -> Result<usize, String> {
+    arg.and_then(|value| value.parse().ok())
+        .filter(|&count| count > 0)
+        .ok_or(format!("{} requires a positive number", flag))
+}
+
+/// Days are numbered 1 to 25, matching the advent calendar
+fn parse_day(arg: Option<&str>) -> Result<u8, String> {
+    let arg = arg.ok_or("A day is required")?;
+
+    arg.parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or(format!("Invalid day '{}', expected 1 to 25", arg))
+}
+
+/// Advent of Code has run every December since 2015
+fn parse_year(arg: Option<&str>) -> Result<u16, String> {
+    let arg = arg.ok_or("--year requires a year")?;
+
+    arg.parse()
+        .ok()
+        .filter(|&year| year >= 2015)
+        .ok_or(format!("Invalid year '{}', expected 2015 or later", arg))
+}
+
+/// Each day has exactly two parts
+fn parse_part(arg: Option<&str>) -> Result<u8, String> {
+    match arg {
+        Some("1") => Ok(1),
+        Some("2") => Ok(2),
+        Some(other) => Err(format!("Invalid part '{}', expected 1 or 2", other)),
+        None => Err("--part requires 1 or 2".to_string()),
+    }
+}
+
+fn expect_no_more_args<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<(), String> {
+    match args.next() {
+        Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn can_parse_run_commands() {
+        assert_eq!(
+            parse_args(&args("run 5")),
+            Ok(Command::Run {
+                day: 5,
+                part: None,
+                input: None,
+                format: OutputFormat::Text
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run --part 2 7 --input res/other.txt")),
+            Ok(Command::Run {
+                day: 7,
+                part: Some(2),
+                input: Some(InputSource::File("res/other.txt".to_string())),
+                format: OutputFormat::Text
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run 7 --input -")),
+            Ok(Command::Run {
+                day: 7,
+                part: None,
+                input: Some(InputSource::Stdin),
+                format: OutputFormat::Text
+            })
+        );
+        assert_eq!(
+            parse_args(&[
+                "run".to_string(),
+                "1".to_string(),
+                "--input-text".to_string(),
+                "1abc2\npqr3stu8vwx".to_string()
+            ]),
+            Ok(Command::Run {
+                day: 1,
+                part: None,
+                input: Some(InputSource::Inline("1abc2\npqr3stu8vwx".to_string())),
+                format: OutputFormat::Text
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run --all")),
+            Ok(Command::RunAll {
+                format: OutputFormat::Text,
+                parallel: false
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run --format jsonl --all --parallel")),
+            Ok(Command::RunAll {
+                format: OutputFormat::JsonLines,
+                parallel: true
+            })
+        );
+    }
+
+    #[test]
+    fn can_parse_other_commands() {
+        assert_eq!(
+            parse_args(&args("batch 5")),
+            Ok(Command::Batch {
+                day: 5,
+                dir: None,
+                part: None,
+                format: OutputFormat::Text
+            })
+        );
+        assert_eq!(
+            parse_args(&args("batch --dir inputs/day-5 5 --part 1 --format json")),
+            Ok(Command::Batch {
+                day: 5,
+                dir: Some("inputs/day-5".to_string()),
+                part: Some(1),
+                format: OutputFormat::Json
+            })
+        );
+        assert_eq!(
+            parse_args(&args("bootstrap 12")),
+            Ok(Command::Bootstrap {
+                day: 12,
+                options: BootstrapOptions::default()
+            })
+        );
+        assert_eq!(
+            parse_args(&args("bootstrap --force 12")),
+            Ok(Command::Bootstrap {
+                day: 12,
+                options: BootstrapOptions {
+                    force: true,
+                    dry_run: false
+                }
+            })
+        );
+        assert_eq!(
+            parse_args(&args("bootstrap 12 --dry-run --force")),
+            Ok(Command::Bootstrap {
+                day: 12,
+                options: BootstrapOptions {
+                    force: true,
+                    dry_run: true
+                }
+            })
+        );
+        assert_eq!(
+            parse_args(&args("submit 5 2")),
+            Ok(Command::Submit { day: 5, part: 2 })
+        );
+        assert_eq!(
+            parse_args(&args("attempt 5 1 too-high 35")),
+            Ok(Command::Attempt {
+                day: 5,
+                part: 1,
+                outcome: Outcome::TooHigh,
+                answer: Answer::Integer(35)
+            })
+        );
+        assert_eq!(
+            parse_args(&args("record 3 --part 1")),
+            Ok(Command::Record {
+                day: 3,
+                part: Some(1)
+            })
+        );
+        assert_eq!(parse_args(&args("verify")), Ok(Command::Verify));
+        assert_eq!(
+            parse_args(&args("bench 8 --iterations 20 --markdown")),
+            Ok(Command::Bench {
+                day: Some(8),
+                options: BenchOptions {
+                    iterations: 20,
+                    markdown: true,
+                    ..BenchOptions::default()
+                }
+            })
+        );
+        assert_eq!(
+            parse_args(&args("bench --all --warmup 1")),
+            Ok(Command::Bench {
+                day: None,
+                options: BenchOptions {
+                    warmup: 1,
+                    ..BenchOptions::default()
+                }
+            })
+        );
+        assert_eq!(
+            parse_args(&args("bench 3 --warmup 0")),
+            Ok(Command::Bench {
+                day: Some(3),
+                options: BenchOptions {
+                    warmup: 0,
+                    ..BenchOptions::default()
+                }
+            })
+        );
+        assert_eq!(
+            parse_args(&args(
+                "bench --all --compare --save-baseline --baseline res/b.txt --threshold 25%"
+            )),
+            Ok(Command::Bench {
+                day: None,
+                options: BenchOptions {
+                    baseline_path: Some("res/b.txt".to_string()),
+                    save_baseline: true,
+                    compare: true,
+                    threshold_percent: 25,
+                    ..BenchOptions::default()
+                }
+            })
+        );
+        assert_eq!(parse_args(&args("help")), Ok(Command::Help));
+        assert_eq!(parse_args(&args("--help")), Ok(Command::Help));
+    }
+
+    #[test]
+    fn can_take_year() {
+        assert_eq!(take_year(&args("run 5")), Ok((None, args("run 5"))));
+        assert_eq!(
+            take_year(&args("run --year 2022 5 --part 1")),
+            Ok((Some(2022), args("run 5 --part 1")))
+        );
+        assert_eq!(
+            take_year(&args("--year 2024 bootstrap 1")),
+            Ok((Some(2024), args("bootstrap 1")))
+        );
+        assert!(take_year(&args("run 5 --year")).is_err());
+        assert!(take_year(&args("run 5 --year 2014")).is_err());
+        assert!(take_year(&args("run 5 --year last")).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let examples = vec![
+            "",
+            "walk 1",
+            "run",
+            "run 0",
+            "run 26",
+            "run one",
+            "run 1 2",
+            "run 1 --part 3",
+            "run 1 --part",
+            "run 1 --input",
+            "run 1 --input-text",
+            "batch",
+            "batch 5 --dir",
+            "batch 5 6",
+            "run --all --input -",
+            "run 1 --verbose",
+            "run 1 --all",
+            "run --all --part 1",
+            "run 3 --parallel",
+            "run --all --format",
+            "run --all --format xml",
+            "record",
+            "record 3 --input res/other.txt",
+            "verify 3",
+            "bench",
+            "bench 3 --all",
+            "bench 3 --iterations 0",
+            "bench 3 --warmup many",
+            "bench 3 --warmup -1",
+            "bench 3 --baseline",
+            "bench 3 --threshold fast",
+            "bootstrap",
+            "bootstrap 10 11",
+            "bootstrap 10 --overwrite",
+            "submit 5",
+            "submit 5 3",
+            "submit 5 1 2",
+            "attempt 5 1 too-high",
+            "attempt 5 1 close 35",
+        ];
+
+        for example in examples {
+            assert!(
+                parse_args(&args(example)).is_err(),
+                "'{}' should be rejected",
+                example
+            );
+        }
+    }
+}
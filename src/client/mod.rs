@@ -0,0 +1,499 @@
+//! Access to the Advent of Code website, behind the [`AocClient`] trait so that the tools built on it can be tested
+//! offline.
+//!
+//! [`HttpClient`] talks to the real website using the user's session cookie, see [`find_session_cookie`] for where
+//! that is looked for. Responses that aren't what was asked for, e.g. a login page because the cookie has expired,
+//! are reported as errors rather than returned. [`FixtureClient`] serves the same
+//! requests from files in a local directory instead. [`CachedClient`] wraps either, so that each day's input is only
+//! ever downloaded once. [`OfflineClient`] makes no requests at all, so that wrapped in a [`CachedClient`] only inputs
+//! that have already been fetched are available, e.g. for a dry run.
+//!
+//! Every request is for a day of a particular year's event, so one client can be used for any year.
+
+use crate::error::{Result, ResultExt};
+use crate::migrate::LEGACY_YEAR;
+use crate::solution::Answer;
+use reqwest::blocking::Client;
+use reqwest::cookie::Jar;
+use reqwest::{StatusCode, Url};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+/// The environment variable checked first for the session cookie
+pub const SESSION_COOKIE_ENV_VAR: &str = "AOC_SESSION";
+
+/// The config file in the user's home directory checked second for the session cookie, shared with other Advent of
+/// Code tools
+pub const SESSION_COOKIE_CONFIG_FILE: &str = ".adventofcode.session";
+
+/// The file within the project checked last for the session cookie
+pub const SESSION_COOKIE_PATH: &str = "res/session_cookie.txt";
+
+/// Where fetched inputs are cached, see [`CachedClient`]
+pub const INPUT_CACHE_DIR: &str = "res/cache";
+
+/// The requests that can be made to the Advent of Code website
+pub trait AocClient {
+    /// The puzzle input for `day` of `year`'s event
+    fn fetch_input(&self, year: u16, day: u8) -> Result<String>;
+
+    /// The puzzle input for `day` of `year`'s event if it is available without making a request, e.g. from a cache
+    fn cached_input(&self, _year: u16, _day: u8) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// The page describing the puzzle for `day` of `year`'s event
+    fn fetch_puzzle(&self, year: u16, day: u8) -> Result<String>;
+
+    /// Submit an answer for one part of a day's puzzle, returning the page the website responds with
+    fn submit_answer(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Result<String>;
+}
+
+/// Makes requests to adventofcode.com, authenticated with a session cookie
+pub struct HttpClient {
+    client: Client,
+    base_url: Url,
+}
+
+impl HttpClient {
+    /// A client for the real website
+    pub fn new(session_cookie: &str) -> Result<HttpClient> {
+        HttpClient::with_base_url(session_cookie, "https://adventofcode.com")
+    }
+
+    /// A client for a server at `base_url`, e.g. a local stand-in for the website
+    pub fn with_base_url(session_cookie: &str, base_url: &str) -> Result<HttpClient> {
+        let base_url: Url = base_url
+            .parse()
+            .chain_err(|| format!("Invalid base URL {}", base_url))?;
+
+        let jar = Jar::default();
+        jar.add_cookie_str(&format!("session={}", session_cookie), &base_url);
+
+        let client = Client::builder()
+            .cookie_store(true)
+            .cookie_provider(Arc::new(jar))
+            .build()?;
+
+        Ok(HttpClient { client, base_url })
+    }
+
+    /// A client for the real website, using the session cookie from [`find_session_cookie`]
+    pub fn from_session() -> Result<HttpClient> {
+        HttpClient::new(&find_session_cookie()?)
+    }
+
+    fn url(&self, path: &str) -> Result<Url> {
+        self.base_url
+            .join(path)
+            .chain_err(|| format!("Invalid URL path {}", path))
+    }
+}
+
+impl AocClient for HttpClient {
+    fn fetch_input(&self, year: u16, day: u8) -> Result<String> {
+        let url = self.url(&format!("/{}/day/{}/input", year, day))?;
+        let response = self.client.get(url).send()?;
+        let status = response.status();
+
+        check_input_response(day, status, response.text()?)
+    }
+
+    fn fetch_puzzle(&self, year: u16, day: u8) -> Result<String> {
+        let url = self.url(&format!("/{}/day/{}", year, day))?;
+        let response = self.client.get(url).send()?;
+        let status = response.status();
+        let body = response.text()?;
+
+        check_status(status, &body)?;
+        Ok(body)
+    }
+
+    fn submit_answer(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Result<String> {
+        let url = self.url(&format!("/{}/day/{}/answer", year, day))?;
+        let response = self
+            .client
+            .post(url)
+            .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+            .send()?;
+        let status = response.status();
+        let body = response.text()?;
+
+        check_status(status, &body)?;
+        Ok(body)
+    }
+}
+
+/// Look for the session cookie in the [`SESSION_COOKIE_ENV_VAR`] environment variable, then the
+/// [`SESSION_COOKIE_CONFIG_FILE`] in the user's home directory, then [`SESSION_COOKIE_PATH`], using the first that is
+/// set.
+pub fn find_session_cookie() -> Result<String> {
+    let read = |path: &Path| fs::read_to_string(path).ok();
+    let home = env::var_os("HOME").map(PathBuf::from);
+
+    let candidates = [
+        (
+            format!("the {} environment variable", SESSION_COOKIE_ENV_VAR),
+            env::var(SESSION_COOKIE_ENV_VAR).ok(),
+        ),
+        (
+            format!("~/{}", SESSION_COOKIE_CONFIG_FILE),
+            home.and_then(|home| read(&home.join(SESSION_COOKIE_CONFIG_FILE))),
+        ),
+        (
+            SESSION_COOKIE_PATH.to_string(),
+            read(Path::new(SESSION_COOKIE_PATH)),
+        ),
+    ];
+
+    for (source, value) in candidates {
+        if let Some(value) = value.filter(|value| !value.trim().is_empty()) {
+            return parse_session_cookie(&value)
+                .chain_err(|| format!("Invalid session cookie in {}", source));
+        }
+    }
+
+    Err(format!(
+        "No session cookie found, set {}, or save it in ~/{} or {}",
+        SESSION_COOKIE_ENV_VAR, SESSION_COOKIE_CONFIG_FILE, SESSION_COOKIE_PATH
+    )
+    .into())
+}
+
+/// Tidy up a session cookie copied from the browser, allowing surrounding whitespace and a `session=` prefix
+fn parse_session_cookie(value: &str) -> Result<String> {
+    let value = value.trim();
+    let value = value.strip_prefix("session=").unwrap_or(value);
+
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("Expected the value of the session cookie, e.g. 53616c7465645f5f...".into());
+    }
+
+    Ok(value.to_string())
+}
+
+/// Only accept a response to an input request if it looks like a puzzle input, rather than an error or login page
+fn check_input_response(day: u8, status: StatusCode, body: String) -> Result<String> {
+    if status == StatusCode::NOT_FOUND {
+        return Err(format!("The input for day {} isn't available yet", day).into());
+    }
+
+    check_status(status, &body)?;
+
+    if body.trim_start().starts_with('<') {
+        return Err("The website responded with a web page rather than a puzzle input".into());
+    }
+
+    Ok(body)
+}
+
+/// Fail if the website rejected the session cookie, or otherwise responded with an error
+fn check_status(status: StatusCode, body: &str) -> Result<()> {
+    if body.contains("Please log in") || status == StatusCode::BAD_REQUEST {
+        return Err("The website didn't accept the session cookie, it may have expired".into());
+    }
+
+    if !status.is_success() {
+        return Err(format!("The website responded with {}", status).into());
+    }
+
+    Ok(())
+}
+
+/// Serves requests from files in a directory, e.g. test fixtures or pages saved from the website. Each year has its own
+/// subdirectory, in which the input for a day is read from `day-N-input.txt`, its puzzle page from `day-N.html`, and
+/// the response to any answer submitted for it from `day-N-answer.html`.
+pub struct FixtureClient {
+    dir: PathBuf,
+}
+
+impl FixtureClient {
+    pub fn new(dir: impl Into<PathBuf>) -> FixtureClient {
+        FixtureClient { dir: dir.into() }
+    }
+
+    fn read(&self, year: u16, filename: String) -> Result<String> {
+        let path = self.dir.join(year.to_string()).join(filename);
+        fs::read_to_string(&path).chain_err(|| format!("No fixture at {}", path.display()))
+    }
+}
+
+impl AocClient for FixtureClient {
+    fn fetch_input(&self, year: u16, day: u8) -> Result<String> {
+        self.read(year, format!("day-{}-input.txt", day))
+    }
+
+    fn fetch_puzzle(&self, year: u16, day: u8) -> Result<String> {
+        self.read(year, format!("day-{}.html", day))
+    }
+
+    fn submit_answer(&self, year: u16, day: u8, _part: u8, _answer: &Answer) -> Result<String> {
+        self.read(year, format!("day-{}-answer.html", day))
+    }
+}
+
+/// Fails every request, for when the website shouldn't be contacted
+pub struct OfflineClient;
+
+impl OfflineClient {
+    fn refuse(request: &str, year: u16, day: u8) -> Result<String> {
+        Err(format!("Not {} for {} day {} while offline", request, year, day).into())
+    }
+}
+
+impl AocClient for OfflineClient {
+    fn fetch_input(&self, year: u16, day: u8) -> Result<String> {
+        OfflineClient::refuse("fetching the input", year, day)
+    }
+
+    fn fetch_puzzle(&self, year: u16, day: u8) -> Result<String> {
+        OfflineClient::refuse("fetching the puzzle", year, day)
+    }
+
+    fn submit_answer(&self, year: u16, day: u8, _part: u8, _answer: &Answer) -> Result<String> {
+        OfflineClient::refuse("submitting an answer", year, day)
+    }
+}
+
+/// When each cached input was fetched, in seconds since the unix epoch, keyed by `(year, day)`
+pub type CacheIndex = BTreeMap<(u16, u8), u64>;
+
+/// Wraps another client, keeping a copy of every input it fetches in a directory, and serving later requests for the
+/// same day from there. The inputs are stored as `<year>/day-N-input.txt`, and `fetched.txt` records when each was
+/// fetched, one `<year> <day> <seconds since the unix epoch>` per line.
+pub struct CachedClient<C> {
+    inner: C,
+    dir: PathBuf,
+}
+
+impl<C: AocClient> CachedClient<C> {
+    pub fn new(inner: C, dir: impl Into<PathBuf>) -> CachedClient<C> {
+        CachedClient {
+            inner,
+            dir: dir.into(),
+        }
+    }
+
+    /// The client used when the cache doesn't have a copy
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day-{}-input.txt", day))
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.dir.join("fetched.txt")
+    }
+
+    /// Load the cache index, treating a missing file as an empty cache
+    fn load_index(&self) -> Result<CacheIndex> {
+        match fs::read_to_string(self.index_path()) {
+            Ok(contents) => parse_cache_index(&contents).map_err(|message| message.into()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(CacheIndex::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn store(&self, year: u16, day: u8, input: &str) -> Result<()> {
+        let mut index = self.load_index()?;
+        index.insert((year, day), seconds_since_epoch());
+
+        fs::create_dir_all(self.dir.join(year.to_string()))?;
+        fs::write(self.input_path(year, day), input)?;
+        fs::write(self.index_path(), format_cache_index(&index))?;
+
+        Ok(())
+    }
+}
+
+impl<C: AocClient> AocClient for CachedClient<C> {
+    fn fetch_input(&self, year: u16, day: u8) -> Result<String> {
+        if let Some(input) = self.cached_input(year, day)? {
+            return Ok(input);
+        }
+
+        let input = self.inner.fetch_input(year, day)?;
+        self.store(year, day, &input)
+            .chain_err(|| format!("Failed to cache the input in {}", self.dir.display()))?;
+
+        Ok(input)
+    }
+
+    fn cached_input(&self, year: u16, day: u8) -> Result<Option<String>> {
+        if let Some(&fetched_at) = self.load_index()?.get(&(year, day)) {
+            if let Ok(input) = fs::read_to_string(self.input_path(year, day)) {
+                println!(
+                    "Using the input for {} day {} cached {} ago",
+                    year,
+                    day,
+                    format_age(seconds_since_epoch().saturating_sub(fetched_at))
+                );
+                return Ok(Some(input));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Puzzle pages are never cached, as they gain part two once part one is solved
+    fn fetch_puzzle(&self, year: u16, day: u8) -> Result<String> {
+        self.inner.fetch_puzzle(year, day)
+    }
+
+    /// Submissions are never cached, as the response depends on what has been submitted before
+    fn submit_answer(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Result<String> {
+        self.inner.submit_answer(year, day, part, answer)
+    }
+}
+
+fn seconds_since_epoch() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn parse_cache_index(contents: &str) -> std::result::Result<CacheIndex, String> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            parse_cache_index_line(line)
+                .ok_or_else(|| format!("Invalid cache index line '{}'", line))
+        })
+        .collect()
+}
+
+/// A line of the index in the format `<year> <day> <seconds since the unix epoch>`, or `<day> <seconds>` from before
+/// inputs were grouped by year, which are all for [`LEGACY_YEAR`]
+fn parse_cache_index_line(line: &str) -> Option<((u16, u8), u64)> {
+    let fields: Vec<&str> = line.split(' ').collect();
+    let (year, day, fetched_at) = match fields[..] {
+        [year, day, fetched_at] => (year.parse().ok()?, day, fetched_at),
+        [day, fetched_at] => (LEGACY_YEAR, day, fetched_at),
+        _ => return None,
+    };
+
+    Some(((year, day.parse().ok()?), fetched_at.parse().ok()?))
+}
+
+fn format_cache_index(index: &CacheIndex) -> String {
+    index
+        .iter()
+        .map(|((year, day), fetched_at)| format!("{} {} {}\n", year, day, fetched_at))
+        .collect()
+}
+
+/// A rough, human readable version of a number of seconds, e.g. `3h 12m`
+fn format_age(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86400, seconds / 3600 % 24, seconds / 60 % 60);
+
+    match (days, hours, minutes) {
+        (0, 0, 0) => "less than a minute".to_string(),
+        (0, 0, minutes) => format!("{}m", minutes),
+        (0, hours, minutes) => format!("{}h {}m", hours, minutes),
+        (days, hours, _) => format!("{}d {}h", days, hours),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::*;
+
+    #[test]
+    fn can_parse_session_cookie() {
+        assert_eq!(
+            parse_session_cookie("53616c7465645f5f\n").unwrap(),
+            "53616c7465645f5f"
+        );
+        assert_eq!(
+            parse_session_cookie(" session=53616c7465645f5f ").unwrap(),
+            "53616c7465645f5f"
+        );
+        assert_eq!(
+            parse_session_cookie("session=abc; Domain=adventofcode.com")
+                .unwrap_err()
+                .describe(),
+            "Expected the value of the session cookie, e.g. 53616c7465645f5f..."
+        );
+    }
+
+    #[test]
+    fn can_check_input_response() {
+        let check = |status: u16, body: &str| {
+            check_input_response(3, StatusCode::from_u16(status).unwrap(), body.to_string())
+        };
+
+        assert_eq!(check(200, "467..114..\n").unwrap(), "467..114..\n");
+        assert_eq!(
+            check(
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!"
+            )
+            .unwrap_err()
+            .describe(),
+            "The input for day 3 isn't available yet"
+        );
+        assert_eq!(
+            check(
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+            )
+            .unwrap_err()
+            .describe(),
+            "The website didn't accept the session cookie, it may have expired"
+        );
+        assert_eq!(
+            check(500, "Internal Server Error").unwrap_err().describe(),
+            "The website responded with 500 Internal Server Error"
+        );
+        assert_eq!(
+            check(200, "<!DOCTYPE html>\n<html lang=\"en-us\">")
+                .unwrap_err()
+                .describe(),
+            "The website responded with a web page rather than a puzzle input"
+        );
+    }
+
+    #[test]
+    fn can_parse_and_format_cache_index() {
+        let index: CacheIndex = vec![((2022, 1), 1669874400), ((2023, 10), 1702184400)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            format_cache_index(&index),
+            "2022 1 1669874400\n2023 10 1702184400\n"
+        );
+        assert_eq!(
+            parse_cache_index("2022 1 1669874400\n\n2023 10 1702184400\n"),
+            Ok(index)
+        );
+        assert_eq!(
+            parse_cache_index("2023 1 yesterday"),
+            Err("Invalid cache index line '2023 1 yesterday'".to_string())
+        );
+        assert_eq!(
+            parse_cache_index("1 1701406800\n"),
+            Ok(vec![((2023, 1), 1701406800)].into_iter().collect())
+        );
+        assert_eq!(
+            parse_cache_index("2023 1 1701406800 2"),
+            Err("Invalid cache index line '2023 1 1701406800 2'".to_string())
+        );
+    }
+
+    #[test]
+    fn can_format_age() {
+        assert_eq!(format_age(59), "less than a minute");
+        assert_eq!(format_age(5 * 60 + 30), "5m");
+        assert_eq!(format_age(3 * 3600 + 12 * 60), "3h 12m");
+        assert_eq!(format_age(2 * 86400 + 3 * 3600 + 12 * 60), "2d 3h");
+    }
+}
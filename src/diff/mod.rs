@@ -0,0 +1,190 @@
+//! Line based diffs between two versions of a file, formatted as a unified diff, e.g. to preview the changes
+//! [`crate::bootstrap_day`] would make to files that already exist.
+
+/// Lines of unchanged context shown around each change
+const CONTEXT: usize = 3;
+
+/// How a line differs between the old and new versions
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+enum Edit {
+    /// Indexes of a line in the old and new versions that is in both
+    Keep(usize, usize),
+    /// Index of a line in the old version that has been removed
+    Remove(usize),
+    /// Index of a line in the new version that has been added
+    Add(usize),
+}
+
+/// A unified diff from `old` to `new`, labelled with `filename`, or an empty string if they have the same lines
+pub fn unified_diff(filename: &str, old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let edits = diff_lines(&old, &new);
+
+    let hunks = group_hunks(&edits);
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    let mut output = format!("--- a/{}\n+++ b/{}\n", filename, filename);
+    for hunk in hunks {
+        output += &format_hunk(hunk, &old, &new);
+    }
+
+    output
+}
+
+/// The shortest list of edits from `old` to `new`, found via their longest common subsequence of lines
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    // lengths[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            edits.push(Edit::Keep(i, j));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            // Prefer removing lines first, so replaced lines are listed before their replacements
+            edits.push(Edit::Remove(i));
+            i += 1;
+        } else {
+            edits.push(Edit::Add(j));
+            j += 1;
+        }
+    }
+
+    edits
+}
+
+/// Split the edits into hunks, each containing one or more changes surrounded by up to [`CONTEXT`] unchanged lines.
+/// Changes close enough for their context to overlap share a hunk.
+fn group_hunks(edits: &[Edit]) -> Vec<&[Edit]> {
+    let changes: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| !matches!(edit, Edit::Keep(..)))
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut hunks = Vec::new();
+    let mut index = 0;
+    while index < changes.len() {
+        let start = changes[index].saturating_sub(CONTEXT);
+        let mut end = changes[index];
+        while index + 1 < changes.len() && changes[index + 1] - end <= 2 * CONTEXT {
+            index += 1;
+            end = changes[index];
+        }
+        hunks.push(&edits[start..(end + CONTEXT + 1).min(edits.len())]);
+        index += 1;
+    }
+
+    hunks
+}
+
+/// A hunk's `@@` header, followed by its lines prefixed with ` `, `-`, or `+`
+fn format_hunk(hunk: &[Edit], old: &[&str], new: &[&str]) -> String {
+    let mut lines = String::new();
+    let (mut old_range, mut new_range) = (None, None);
+    let extend = |range: &mut Option<(usize, usize)>, index: usize| {
+        *range = Some(range.map_or((index, 1), |(start, count)| (start, count + 1)));
+    };
+
+    for &edit in hunk {
+        match edit {
+            Edit::Keep(i, j) => {
+                extend(&mut old_range, i);
+                extend(&mut new_range, j);
+                lines += &format!(" {}\n", old[i]);
+            }
+            Edit::Remove(i) => {
+                extend(&mut old_range, i);
+                lines += &format!("-{}\n", old[i]);
+            }
+            Edit::Add(j) => {
+                extend(&mut new_range, j);
+                lines += &format!("+{}\n", new[j]);
+            }
+        }
+    }
+
+    format!(
+        "@@ -{} +{} @@\n{}",
+        format_range(old_range),
+        format_range(new_range),
+        lines
+    )
+}
+
+/// A range in a hunk header, `<first line>,<count>` counting lines from 1. A hunk only lacks lines from one of the
+/// versions if that version is empty, which is given as `0,0`.
+fn format_range(range: Option<(usize, usize)>) -> String {
+    match range {
+        Some((start, 1)) => format!("{}", start + 1),
+        Some((start, count)) => format!("{},{}", start + 1, count),
+        None => "0,0".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::*;
+
+    #[test]
+    fn can_diff_identical_files() {
+        assert_eq!(unified_diff("a.txt", "one\ntwo\n", "one\ntwo\n"), "");
+        assert_eq!(unified_diff("a.txt", "", ""), "");
+    }
+
+    #[test]
+    fn can_diff_changed_lines() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n";
+        let new = "1\n2\n3\n4\n5\nsix\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n";
+
+        assert_eq!(
+            unified_diff("numbers.txt", old, new),
+            "\
+--- a/numbers.txt
++++ b/numbers.txt
+@@ -3,7 +3,7 @@
+ 3
+ 4
+ 5
+-6
++six
+ 7
+ 8
+ 9
+@@ -13,3 +13,4 @@
+ 13
+ 14
+ 15
++16
+"
+        );
+    }
+
+    #[test]
+    fn can_diff_new_and_removed_files() {
+        assert_eq!(
+            unified_diff("new.txt", "", "one\ntwo\n"),
+            "--- a/new.txt\n+++ b/new.txt\n@@ -0,0 +1,2 @@\n+one\n+two\n"
+        );
+        assert_eq!(
+            unified_diff("old.txt", "one\n", ""),
+            "--- a/old.txt\n+++ b/old.txt\n@@ -1 +0,0 @@\n-one\n"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! The error type shared by the whole project, so that failures in bootstrapping, reading inputs, parsing, or solving
+//! a puzzle can all propagate up to the binary's `main` and be reported cleanly.
+
+use crate::parser::ParseError;
+use error_chain::error_chain;
+
+error_chain! {
+    foreign_links {
+        Io(std::io::Error);
+        HttpRequest(reqwest::Error);
+        Parse(ParseError);
+    }
+
+    errors {
+        // A solution couldn't find an answer for an input that was otherwise valid
+        Solver(message: String) {
+            description("no answer found")
+            display("{}", message)
+        }
+    }
+}
+
+impl Error {
+    /// Shorthand for an [`ErrorKind::Solver`] error
+    pub fn solver(message: impl Into<String>) -> Error {
+        ErrorKind::Solver(message.into()).into()
+    }
+
+    /// If this is a [`ParseError`], work out where it occurred in `input`, see [`ParseError::locate`]
+    pub fn locate(self, input: &str) -> Error {
+        match self {
+            Error(ErrorKind::Parse(error), state) => {
+                Error(ErrorKind::Parse(error.locate(input)), state)
+            }
+            error => error,
+        }
+    }
+
+    /// A description of the error, and anything that caused it, suitable to show the user. Parse errors include a
+    /// diagnostic pointing at the offending input.
+    pub fn describe(&self) -> String {
+        match self.kind() {
+            ErrorKind::Parse(error) => format!("Invalid input\n{}", error.diagnostic()),
+            _ => self
+                .iter()
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>()
+                .join("\n  caused by: "),
+        }
+    }
+
+    /// A single line description of the error, and anything that caused it, for output that can't span several lines,
+    /// e.g. a table cell. Parse errors give their location rather than a diagnostic.
+    pub fn summary(&self) -> String {
+        match self.kind() {
+            ErrorKind::Parse(error) => format!("Invalid input: {}", error),
+            _ => self
+                .iter()
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>()
+                .join(", caused by: "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::*;
+    use crate::parser::parse_number;
+
+    #[test]
+    fn can_describe_errors() {
+        assert_eq!(
+            Error::solver("No route from AAA to ZZZ").describe(),
+            "No route from AAA to ZZZ"
+        );
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+        let error = Error::from(io_error).chain_err(|| "Failed to read res/day-1-input.txt");
+        assert_eq!(
+            error.describe(),
+            "Failed to read res/day-1-input.txt\n  caused by: no such file"
+        );
+        assert_eq!(
+            error.summary(),
+            "Failed to read res/day-1-input.txt, caused by: no such file"
+        );
+    }
+
+    #[test]
+    fn can_locate_parse_errors() {
+        let input = "1 2 x";
+        let error = Error::from(parse_number::<u8>(&input[4..]).unwrap_err()).locate(input);
+
+        assert_eq!(
+            error.describe(),
+            "\
+Invalid input
+Expected a number, found 'x'
+ --> line 1, column 5
+  |
+1 | 1 2 x
+  |     ^"
+        );
+        assert_eq!(
+            error.summary(),
+            "Invalid input: Line 1, column 5: Expected a number, found 'x'"
+        );
+    }
+}
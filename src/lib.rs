@@ -0,0 +1,38 @@
+//! My solutions for [Advent of Code](https://adventofcode.com), along with the tools for running, benchmarking, and
+//! bootstrapping them.
+//!
+//! Each event has its own module, e.g. [`y2023`], and each day is a public module within that, e.g.
+//! [`y2023::day_5`], exposing its parser, the types it parses into, and the functions that solve each part, so they
+//! can be reused outside of the puzzle runner. Every day also provides a [`solution::Solution`], and these are
+//! collected in [`YEARS`] for the command line tool in `main.rs` to run.
+//!
+//! The helpers that each day's parsing and solving are built from stay private, but the write-ups still walk through
+//! them, so the docs are built with `--document-private-items` and links to private items are allowed.
+
+#![allow(rustdoc::private_intra_doc_links)]
+
+pub mod answers;
+pub mod bench;
+pub mod bootstrap_day;
+pub mod cli;
+pub mod client;
+pub mod diff;
+pub mod error;
+mod helpers;
+pub mod migrate;
+pub mod parser;
+pub mod puzzle;
+pub mod report;
+pub mod runner;
+pub mod solution;
+pub mod submit;
+pub mod template;
+pub mod y2023;
+
+use solution::Years;
+
+/// Each event's registered solutions, keyed by year. Kept one per line so that bootstrapping a new year can add to it.
+#[rustfmt::skip]
+pub static YEARS: &Years = &[
+    (2023, y2023::SOLUTIONS),
+];
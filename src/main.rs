@@ -1,55 +1,227 @@
-mod bootstrap_day;
-mod day_1;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-mod helpers;
-
-use bootstrap_day::bootstrap_day;
+//! The command line tool for running my Advent of Code solutions, see [`advent_of_code_2023`] for the solutions
+//! themselves.
+
+use advent_of_code_2023::answers::{answers_path, load_answers};
+use advent_of_code_2023::bootstrap_day::{bootstrap_day, BootstrapOptions};
+use advent_of_code_2023::cli::{self, Command};
+use advent_of_code_2023::client::{
+    AocClient, CachedClient, HttpClient, OfflineClient, INPUT_CACHE_DIR,
+};
+use advent_of_code_2023::error::{Result, ResultExt};
+use advent_of_code_2023::migrate::migrate_legacy_layout;
+use advent_of_code_2023::report::OutputFormat;
+use advent_of_code_2023::runner::{self, InputSource};
+use advent_of_code_2023::solution::{find_solution, find_year, latest_year, Registry};
+use advent_of_code_2023::submit::{
+    record_submission, submissions_path, submit_day, Outcome, Submission,
+};
+use advent_of_code_2023::YEARS;
+use std::env;
 use std::io::{self, Write};
+use std::path::Path;
+use std::process::exit;
 use std::time::Instant;
 
-extern crate core;
-
-extern crate itertools;
 #[macro_use]
 extern crate text_io;
 
 fn main() {
-    print!("Which day? (0 to run all): ");
-    io::stdout().flush().unwrap();
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (year, args) = cli::take_year(&args).unwrap_or_else(|message| exit_with_usage(&message));
+    let year = year
+        .or_else(|| latest_year(YEARS))
+        .expect("At least one year has solutions");
+    let command = if args.is_empty() {
+        prompt_for_command(year)
+    } else {
+        cli::parse_args(&args).unwrap_or_else(|message| exit_with_usage(&message))
+    };
 
-    let day: u8 = read!();
-    let days: Vec<Box<dyn Fn() -> ()>> = vec![
-        Box::new(|| day_1::run()),
-        Box::new(|| day_2::run()),
-        Box::new(|| day_3::run()),
-        Box::new(|| day_4::run()),
-        Box::new(|| day_5::run()),
-        Box::new(|| day_6::run()),
-        Box::new(|| day_7::run()),
-        Box::new(|| day_8::run()),
-        Box::new(|| day_9::run()),
-    ];
+    // Machine-readable output should only contain the report
+    let show_timing = !matches!(
+        command,
+        Command::Run {
+            format: OutputFormat::Json | OutputFormat::JsonLines,
+            ..
+        } | Command::RunAll {
+            format: OutputFormat::Json | OutputFormat::JsonLines,
+            ..
+        } | Command::Batch {
+            format: OutputFormat::Json | OutputFormat::JsonLines,
+            ..
+        }
+    );
+
+    if let Err(error) = migrate_legacy_layout(Path::new(".")) {
+        eprintln!("Warning: {}", error.describe());
+    }
 
     let start = Instant::now();
-    match days.get((day - 1) as usize) {
-        Some(solution) => solution(),
-        None if day == 0 => days.iter().enumerate().for_each(|(i, solution)| {
-            let start = Instant::now();
-            println!("==== Day {} ====", i + 1);
-            solution();
-            println!("-- took {:.2?}", start.elapsed());
-        }),
-        None if day >= 1 && day <= 25 => bootstrap_day(day).expect("Failed to bootstrap day"),
-        None => println!("Invalid Day {}", day),
+    let success = run_command(year, command).unwrap_or_else(|error| {
+        eprintln!("Error: {}", error.describe());
+        exit(1)
+    });
+
+    if show_timing {
+        println!();
+        println!("Finished in {:.2?}", start.elapsed());
+    }
+
+    if !success {
+        exit(1)
     }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!();
+    eprintln!("{}", cli::USAGE);
+    exit(2)
+}
 
-    println!();
-    println!("Finished in {:.2?}", start.elapsed());
+/// The solutions registered for `year`, failing if none have been bootstrapped yet
+fn solutions_for(year: u16) -> Result<&'static Registry> {
+    Ok(find_year(YEARS, year).ok_or_else(|| {
+        format!(
+            "There are no solutions for {} yet, run `bootstrap --year {} <day>` first",
+            year, year
+        )
+    })?)
+}
+
+/// Carry out a command for `year`'s event, returning whether everything it ran succeeded
+fn run_command(year: u16, command: Command) -> Result<bool> {
+    let success = match command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+        } => {
+            let solution = find_solution(solutions_for(year)?, day).ok_or_else(|| {
+                format!(
+                    "Day {} has no solution yet, run `bootstrap --year {} {}` first",
+                    day, year, day
+                )
+            })?;
+            let input = input.unwrap_or_else(|| InputSource::default_for(year, day));
+            runner::run_day(year, day, solution, &input, part, format)
+        }
+        Command::RunAll { format, parallel } => {
+            runner::run_all(year, solutions_for(year)?, format, parallel)
+        }
+        Command::Batch {
+            day,
+            dir,
+            part,
+            format,
+        } => {
+            let solution = find_solution(solutions_for(year)?, day)
+                .ok_or_else(|| format!("Day {} has no solution to run", day))?;
+            let dir = dir.unwrap_or_else(|| runner::default_batch_dir(year, day));
+            runner::run_batch(day, solution, Path::new(&dir), part, format)?
+        }
+        Command::Record { day, part } => {
+            let solution = find_solution(solutions_for(year)?, day)
+                .ok_or_else(|| format!("Day {} has no solution to record", day))?;
+            runner::record_day(year, day, solution, part, Path::new(&answers_path(year)))
+                .chain_err(|| "Failed to record answers")?;
+            true
+        }
+        Command::Verify => {
+            let answers = load_answers(Path::new(&answers_path(year)))
+                .chain_err(|| "Failed to load recorded answers")?;
+            runner::verify(year, solutions_for(year)?, &answers)
+        }
+        Command::Bench { day, options } => {
+            let solutions = solutions_for(year)?;
+            let days: Vec<u8> = match day {
+                Some(day) if find_solution(solutions, day).is_none() => {
+                    return Err(format!("Day {} has no solution to benchmark", day).into())
+                }
+                Some(day) => vec![day],
+                None => solutions.iter().map(|&(day, _)| day).collect(),
+            };
+            runner::bench(year, solutions, &days, &options)
+                .chain_err(|| "Failed to access the baseline")?
+        }
+        Command::Bootstrap { day, options } => {
+            // A dry run only uses inputs that have already been cached, so doesn't need the session cookie
+            let client: Box<dyn AocClient> = if options.dry_run {
+                Box::new(CachedClient::new(OfflineClient, INPUT_CACHE_DIR))
+            } else {
+                Box::new(CachedClient::new(
+                    HttpClient::from_session()?,
+                    INPUT_CACHE_DIR,
+                ))
+            };
+            bootstrap_day(client.as_ref(), Path::new("."), year, day, &options)
+                .chain_err(|| "Failed to bootstrap day")?;
+            true
+        }
+        Command::Submit { day, part } => {
+            let solution = find_solution(solutions_for(year)?, day)
+                .ok_or_else(|| format!("Day {} has no solution to submit", day))?;
+            let client = HttpClient::from_session()?;
+            let outcome = submit_day(&client, Path::new("."), year, day, solution, part)
+                .chain_err(|| "Failed to submit answer")?;
+
+            println!("{}", outcome);
+            matches!(outcome, Outcome::Correct | Outcome::AlreadyComplete)
+        }
+        Command::Attempt {
+            day,
+            part,
+            outcome,
+            answer,
+        } => {
+            let path = submissions_path(year, day);
+            let submission = Submission {
+                part,
+                outcome,
+                answer,
+            };
+            record_submission(Path::new(&path), &submission)
+                .chain_err(|| format!("Failed to record the attempt in {}", path))?;
+
+            println!("Attempt recorded in {}", path);
+            true
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            true
+        }
+    };
+
+    Ok(success)
+}
+
+/// The interactive fallback when no arguments are given. Days of `year` with a solution are run, other valid days are
+/// bootstrapped, and 0 runs everything.
+fn prompt_for_command(year: u16) -> Command {
+    print!("Which day? (0 to run all): ");
+    io::stdout().flush().unwrap();
+
+    let day: u8 = read!();
+    let solutions = find_year(YEARS, year).unwrap_or(&[]);
+    match day {
+        0 => Command::RunAll {
+            format: OutputFormat::Text,
+            parallel: false,
+        },
+        day if find_solution(solutions, day).is_some() => Command::Run {
+            day,
+            part: None,
+            input: None,
+            format: OutputFormat::Text,
+        },
+        day if day <= 25 => Command::Bootstrap {
+            day,
+            options: BootstrapOptions::default(),
+        },
+        day => {
+            println!("Invalid Day {}", day);
+            exit(1)
+        }
+    }
 }
@@ -0,0 +1,89 @@
+//! Moves files saved before the project was organised by year into the per-year layout, so that upgrading doesn't
+//! orphan inputs, recorded answers, submissions, or benchmark baselines. Everything saved before then was for
+//! [`LEGACY_YEAR`]'s event, and keeps its name, e.g. `res/day-1-input.txt` moves to `res/2023/day-1-input.txt`.
+//!
+//! The same applies to the inputs in [`INPUT_CACHE_DIR`], and the cache index's old `<day> <seconds>` lines are read
+//! as being for [`LEGACY_YEAR`], see [`crate::client::CachedClient`].
+
+use crate::client::INPUT_CACHE_DIR;
+use crate::error::{Result, ResultExt};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// The only year the project supported before files were grouped by year
+pub const LEGACY_YEAR: u16 = 2023;
+
+/// Matches the name of a file or directory saved in the legacy layout
+const LEGACY_NAME_PATTERN: &str =
+    r"^((answers|bench-baseline|day-\d+-(input|example-\d+|submissions))\.txt|day-\d+)$";
+
+/// Move any files in the project at `root` that are still in the legacy layout, reporting each move on stderr so that
+/// machine-readable output is unaffected. A file is left where it is if one already exists at its new path.
+pub fn migrate_legacy_layout(root: &Path) -> Result<()> {
+    let pattern = Regex::new(LEGACY_NAME_PATTERN).unwrap();
+
+    for dir in ["res", INPUT_CACHE_DIR] {
+        let Ok(entries) = fs::read_dir(root.join(dir)) else {
+            continue;
+        };
+
+        let mut names = Vec::new();
+        for entry in entries {
+            if let Ok(name) = entry?.file_name().into_string() {
+                if pattern.is_match(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names.sort();
+
+        for name in names {
+            let from = format!("{}/{}", dir, name);
+            let to = format!("{}/{}/{}", dir, LEGACY_YEAR, name);
+
+            if root.join(&to).exists() {
+                eprintln!("Warning: {} was not moved, as {} already exists", from, to);
+                continue;
+            }
+
+            fs::create_dir_all(root.join(dir).join(LEGACY_YEAR.to_string()))
+                .and_then(|_| fs::rename(root.join(&from), root.join(&to)))
+                .chain_err(|| format!("Failed to move {} to {}", from, to))?;
+            eprintln!("Moved {} to {}", from, to);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::migrate::*;
+
+    #[test]
+    fn can_match_legacy_names() {
+        let pattern = Regex::new(LEGACY_NAME_PATTERN).unwrap();
+
+        for name in [
+            "answers.txt",
+            "bench-baseline.txt",
+            "day-1-input.txt",
+            "day-12-example-2.txt",
+            "day-5-submissions.txt",
+            "day-7",
+        ] {
+            assert!(pattern.is_match(name), "{} should be moved", name);
+        }
+
+        for name in [
+            "2023",
+            "cache",
+            "fetched.txt",
+            "session_cookie.txt",
+            "day-1-input.txt.bak",
+        ] {
+            assert!(!pattern.is_match(name), "{} should not be moved", name);
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Shared tools for parsing puzzle inputs without panicking, so that a corrupted or truncated input file is reported
+//! with the position of the problem.
+//!
+//! Day parsers return [`ParseError`]s created from the offending slice of the input. As long as that slice was
+//! borrowed from the puzzle input, [`ParseError::locate`] can then work out the line and column it came from, which
+//! [`crate::solution::DaySolution`] does for every day. [`ParseError::diagnostic`] renders a caret pointing at the
+//! problem, e.g.
+//!
+//! ```text
+//! Expected a number, found 'x'
+//!  --> line 3, column 9
+//!   |
+//! 3 | Game 3: x red
+//!   |         ^
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Where in the puzzle input a [`ParseError`] occurred
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Location {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    /// The full text of the line containing the error
+    pub line_text: String,
+}
+
+/// Describes why part of the puzzle input could not be parsed
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct ParseError {
+    pub message: String,
+    /// The offending text, empty if something was missing
+    pub text: String,
+    /// Only known once [`ParseError::locate`] has been called with the input `text` was borrowed from
+    pub location: Option<Location>,
+    /// The address of `text` within the input, used to calculate the location. This is never dereferenced.
+    address: usize,
+}
+
+impl ParseError {
+    /// An error for a slice of the puzzle input. To be located, `text` must be borrowed from the input, rather than
+    /// an owned copy.
+    pub fn new(text: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            text: text.to_string(),
+            location: None,
+            address: text.as_ptr() as usize,
+        }
+    }
+
+    /// Work out the line and column of the error, if the offending text was borrowed from `input`
+    pub fn locate(mut self, input: &str) -> ParseError {
+        let offset = self.address.wrapping_sub(input.as_ptr() as usize);
+        if self.location.is_some() || offset > input.len() || !input.is_char_boundary(offset) {
+            return self;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |pos| pos + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |pos| offset + pos);
+
+        self.location = Some(Location {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            line_text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        });
+
+        self
+    }
+
+    /// A multi-line description of the error, with a caret pointing at the offending text if it has been located
+    pub fn diagnostic(&self) -> String {
+        let Some(location) = &self.location else {
+            return self.message.clone();
+        };
+
+        let line_number = location.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let caret_offset = " ".repeat(location.column - 1);
+        let carets = "^".repeat(
+            self.text
+                .lines()
+                .next()
+                .unwrap_or("")
+                .chars()
+                .count()
+                .max(1),
+        );
+
+        format!(
+            "{}\n{}--> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            self.message,
+            gutter,
+            location.line,
+            location.column,
+            gutter,
+            line_number,
+            location.line_text,
+            gutter,
+            caret_offset,
+            carets
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(
+                f,
+                "Line {}, column {}: {}",
+                location.line, location.column, self.message
+            ),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// [`str::split_once`], failing if the delimiter is missing. The error points at the end of `text`, having searched up
+/// to there for the delimiter.
+pub fn split_once<'a>(text: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(delimiter).ok_or_else(|| {
+        ParseError::new(
+            &text[text.len()..],
+            format!(
+                "Expected '{}' in '{}'",
+                delimiter.escape_debug(),
+                excerpt(text)
+            ),
+        )
+    })
+}
+
+/// [`str::strip_prefix`], failing if the prefix is missing
+pub fn strip_prefix<'a>(text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    text.strip_prefix(prefix).ok_or_else(|| {
+        let found = &text[..text
+            .char_indices()
+            .nth(prefix.chars().count())
+            .map_or(text.len(), |(pos, _)| pos)];
+        ParseError::new(found, format!("Expected '{}', found '{}'", prefix, found))
+    })
+}
+
+/// [`str::strip_suffix`], failing if the suffix is missing
+pub fn strip_suffix<'a>(text: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+    text.strip_suffix(suffix).ok_or_else(|| {
+        ParseError::new(
+            &text[text.len()..],
+            format!("Expected '{}' at the end of '{}'", suffix, excerpt(text)),
+        )
+    })
+}
+
+/// Parse a number, ignoring surrounding whitespace
+pub fn parse_number<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    let text = text.trim();
+    text.parse()
+        .map_err(|_| ParseError::new(text, format!("Expected a number, found '{}'", text)))
+}
+
+/// Parse a whitespace separated list of numbers
+pub fn parse_numbers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    text.split_whitespace().map(parse_number).collect()
+}
+
+/// The most of a slice of the input that is quoted in an error message, so a large section isn't dumped in full
+const EXCERPT_LENGTH: usize = 40;
+
+/// The start of `text` to quote in an error message: its first line, truncated to [`EXCERPT_LENGTH`] characters, with
+/// `...` marking anything left out
+fn excerpt(text: &str) -> String {
+    let first_line = text.lines().next().unwrap_or("");
+    let quoted: String = first_line.chars().take(EXCERPT_LENGTH).collect();
+
+    if quoted.len() < text.trim_end().len() {
+        format!("{}...", quoted)
+    } else {
+        quoted
+    }
+}
+
+/// The next line of the input, or an error pointing at the end of the input if it is missing
+pub fn next_line<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    input: &'a str,
+    description: &str,
+) -> Result<&'a str, ParseError> {
+    lines.next().ok_or_else(|| {
+        ParseError::new(
+            &input[input.len()..],
+            format!("Unexpected end of input, expected {}", description),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::*;
+
+    #[test]
+    fn can_locate_errors() {
+        let input = "Game 1: 3 blue\nGame 2: x red\n";
+        let error = parse_number::<u8>(&input[23..25])
+            .unwrap_err()
+            .locate(input);
+
+        assert_eq!(error.text, "x");
+        assert_eq!(
+            error.location,
+            Some(Location {
+                line: 2,
+                column: 9,
+                line_text: "Game 2: x red".to_string()
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "Line 2, column 9: Expected a number, found 'x'"
+        );
+    }
+
+    #[test]
+    fn ignores_text_from_elsewhere() {
+        let input = "Game 1: 3 blue";
+        let other = "x".to_string();
+        let error = parse_number::<u8>(&other).unwrap_err().locate(input);
+
+        assert_eq!(error.location, None);
+        assert_eq!(error.diagnostic(), "Expected a number, found 'x'");
+    }
+
+    #[test]
+    fn can_format_diagnostic() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 twelve\n";
+        let line = input.lines().nth(3).unwrap();
+        let error = parse_numbers::<i64>(line).unwrap_err().locate(input);
+
+        assert_eq!(
+            error.diagnostic(),
+            "\
+Expected a number, found 'twelve'
+ --> line 4, column 7
+  |
+4 | 50 98 twelve
+  |       ^^^^^^"
+        );
+    }
+
+    #[test]
+    fn can_split_and_strip() {
+        assert_eq!(split_once("a = b", " = "), Ok(("a", "b")));
+        assert_eq!(
+            split_once("a - b", " = ").unwrap_err().message,
+            "Expected ' = ' in 'a - b'"
+        );
+
+        let input = "seeds: 79 14 55 13\nseed-to-soil map:\n50 98 2\n";
+        let error = split_once(input, "\n\n").unwrap_err().locate(input);
+        assert_eq!(
+            error.message,
+            "Expected '\\n\\n' in 'seeds: 79 14 55 13...'"
+        );
+        assert_eq!(error.location.map(|location| location.line), Some(4));
+
+        assert_eq!(strip_prefix("Game 12", "Game "), Ok("12"));
+        let error = strip_prefix("Gmae 12", "Game ").unwrap_err();
+        assert_eq!(error.message, "Expected 'Game ', found 'Gmae '");
+        assert_eq!(error.text, "Gmae ");
+
+        assert_eq!(strip_suffix("(BBB)", ")"), Ok("(BBB"));
+        assert_eq!(
+            strip_suffix("(BBB", ")").unwrap_err().message,
+            "Expected ')' at the end of '(BBB'"
+        );
+        assert_eq!(
+            strip_suffix(&"A".repeat(50), ")").unwrap_err().message,
+            format!("Expected ')' at the end of '{}...'", "A".repeat(40))
+        );
+    }
+
+    #[test]
+    fn can_report_missing_lines() {
+        let input = "Time: 7";
+        let mut lines = input.lines();
+        assert_eq!(next_line(&mut lines, input, "times"), Ok("Time: 7"));
+
+        let error = next_line(&mut lines, input, "distances")
+            .unwrap_err()
+            .locate(input);
+        assert_eq!(error.message, "Unexpected end of input, expected distances");
+        assert_eq!(error.location.map(|location| location.column), Some(8));
+    }
+}
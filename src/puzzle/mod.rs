@@ -0,0 +1,75 @@
+//! Picks out the details [`crate::bootstrap_day`] needs from a day's puzzle page on the Advent of Code website.
+
+use regex::Regex;
+
+/// The puzzle's title, from the `<h2>--- Day 1: Trebuchet?! ---</h2>` heading that starts its description
+pub fn parse_title(page: &str) -> Option<String> {
+    Regex::new(r"<h2>--- Day \d+: (.+?) ---</h2>")
+        .unwrap()
+        .captures(page)
+        .map(|captures| decode_entities(captures[1].trim()))
+}
+
+/// The contents of each `<pre><code>` block in the puzzle description, which is how the examples are presented. Any
+/// highlighting within them is removed.
+pub fn parse_examples(page: &str) -> Vec<String> {
+    let markup = Regex::new(r"<[^>]*>").unwrap();
+
+    Regex::new(r"(?s)<pre><code>(.*?)</code></pre>")
+        .unwrap()
+        .captures_iter(page)
+        .map(|captures| decode_entities(&markup.replace_all(&captures[1], "")))
+        .collect()
+}
+
+/// Replace the HTML entities the website uses with the characters they represent
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzle::*;
+
+    #[test]
+    fn can_parse_title() {
+        let page = "\
+<main>
+<article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is wrong with global snow production</p>
+</article>
+</main>";
+
+        assert_eq!(parse_title(page), Some("Trebuchet?!".to_string()));
+        assert_eq!(
+            parse_title("<h2>--- Day 12: Hot Springs &amp; Things ---</h2>"),
+            Some("Hot Springs & Things".to_string())
+        );
+        assert_eq!(parse_title("<h2>Please log in</h2>"), None);
+    }
+
+    #[test]
+    fn can_parse_examples() {
+        let page = "\
+<p>For example:</p>
+<pre><code>467..114..
+...*......
+</code></pre>
+<p>In this schematic, <code>114</code> is not adjacent to a symbol.</p>
+<pre><code>Card 1: <em>41</em> 48 | 83 86
+a &lt;- b &amp;&amp; c
+</code></pre>";
+
+        assert_eq!(
+            parse_examples(page),
+            vec![
+                "467..114..\n...*......\n".to_string(),
+                "Card 1: 41 48 | 83 86\na <- b && c\n".to_string()
+            ]
+        );
+        assert_eq!(parse_examples("<p>No examples</p>"), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,511 @@
+//! Formats the outcome of running days, either as human-readable text, or as JSON for other tools (e.g. the blog
+//! build) to consume.
+//!
+//! The JSON for a single day looks like:
+//!
+//! ```json
+//! {"day":1,"input":"res/2023/day-1-input.txt","status":"ok","error":null,"nanos":4512,"parse_nanos":337,
+//!  "parts":[{"part":1,"answer":54632,"error":null,"nanos":1330},
+//!           {"part":2,"answer":"text answer","error":null,"nanos":1700}]}
+//! ```
+//!
+//! `status` is one of `ok`, `missing-input` or `failed`, with `error` describing the [`Failure`]. A part that couldn't
+//! be solved has a `null` answer and its own `error`, and makes the day's status `failed`, but the other parts'
+//! answers are still included.
+//!
+//! [`OutputFormat::Json`] wraps the days in `{"days":[...],"total_nanos":...}`, whereas [`OutputFormat::JsonLines`]
+//! outputs one day per line as it completes.
+
+use crate::solution::{Answer, DayResult};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How the results of running days should be output
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    JsonLines,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "jsonl" => Ok(OutputFormat::JsonLines),
+            _ => Err(format!(
+                "Invalid format '{}', expected text, json or jsonl",
+                s
+            )),
+        }
+    }
+}
+
+/// Why a day didn't produce its answers
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Failure {
+    /// The input file doesn't exist, usually because the day hasn't been bootstrapped on this machine
+    MissingInput(String),
+    /// The input couldn't be read, or the solution panicked while parsing it
+    Failed(String),
+    /// The solution couldn't parse the input, as a single line [summary](crate::error::Error::summary) and a
+    /// [description](crate::error::Error::describe) which may point at the problem across several lines
+    InvalidInput {
+        summary: String,
+        description: String,
+    },
+}
+
+impl Failure {
+    fn status(&self) -> &'static str {
+        match self {
+            Failure::MissingInput(_) => "missing-input",
+            Failure::Failed(_) | Failure::InvalidInput { .. } => "failed",
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::MissingInput(message) => write!(f, "Missing input: {}", message),
+            Failure::Failed(message) => write!(f, "{}", message),
+            Failure::InvalidInput { summary, .. } => write!(f, "{}", summary),
+        }
+    }
+}
+
+/// The outcome of running a single day against an input file
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: u8,
+    /// A description of where the input was read from, usually the file path
+    pub input: String,
+    /// The answers and timings, or a description of what went wrong
+    pub outcome: Result<DayResult, Failure>,
+    /// The total time taken, including reading the input
+    pub duration: Duration,
+}
+
+impl DayReport {
+    /// Whether the day ran, and solved every requested part
+    pub fn succeeded(&self) -> bool {
+        self.outcome.as_ref().is_ok_and(DayResult::is_solved)
+    }
+}
+
+/// The answers, or the error, as lines of text
+pub fn format_text(report: &DayReport) -> String {
+    match &report.outcome {
+        Ok(result) => {
+            let mut output = format!("Parsed input in {:.2?}\n", result.parse_duration);
+            for part in &result.parts {
+                let answer = match &part.answer {
+                    Ok(answer) => answer.to_string(),
+                    Err(error) => format!("FAILED: {}", error.describe()),
+                };
+                output.push_str(&format!(
+                    "Part {}: {} (in {:.2?})\n",
+                    part.part, answer, part.duration
+                ));
+            }
+            output
+        }
+        Err(Failure::InvalidInput { description, .. }) => format!("FAILED: {}\n", description),
+        Err(failure) => format!("FAILED: {}\n", failure),
+    }
+}
+
+/// A summary of which days succeeded, failed, or were missing input, for the end of a run of several days
+pub fn format_summary(reports: &[DayReport]) -> String {
+    let days_matching = |matches: fn(&DayReport) -> bool| -> Vec<String> {
+        reports
+            .iter()
+            .filter(|report| matches(report))
+            .map(|report| report.day.to_string())
+            .collect()
+    };
+
+    let groups = [
+        ("Succeeded", days_matching(DayReport::succeeded)),
+        (
+            "Failed",
+            days_matching(|report| match &report.outcome {
+                Ok(result) => !result.is_solved(),
+                Err(failure) => !matches!(failure, Failure::MissingInput(_)),
+            }),
+        ),
+        (
+            "Missing input",
+            days_matching(|report| matches!(report.outcome, Err(Failure::MissingInput(_)))),
+        ),
+    ];
+
+    groups
+        .iter()
+        .map(|(label, days)| {
+            if days.is_empty() {
+                format!("{}: none\n", label)
+            } else {
+                format!("{}: {} (days {})\n", label, days.len(), days.join(", "))
+            }
+        })
+        .collect()
+}
+
+/// A markdown table with a row per input, for comparing the results of running one day against several inputs
+pub fn format_batch_table(reports: &[DayReport]) -> String {
+    let mut output = "\
+| Input | Part 1 | Time | Part 2 | Time | Total |
+|:------|-------:|-----:|-------:|-----:|------:|
+"
+    .to_string();
+
+    for report in reports {
+        let parts = match &report.outcome {
+            Ok(result) => [1, 2]
+                .iter()
+                .map(|&part| {
+                    result
+                        .parts
+                        .iter()
+                        .find(|result| result.part == part)
+                        .map(|result| match &result.answer {
+                            Ok(answer) => format!("{} | {:.2?}", answer, result.duration),
+                            Err(error) => {
+                                format!("FAILED: {} | {:.2?}", error.summary(), result.duration)
+                            }
+                        })
+                        .unwrap_or("- | -".to_string())
+                })
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Err(failure) => format!("FAILED: {} | | |", failure),
+        };
+
+        output.push_str(&format!(
+            "| {} | {} | {:.2?} |\n",
+            report.input, parts, report.duration
+        ));
+    }
+
+    output
+}
+
+/// A single day as a JSON object, see the [module documentation](self) for the shape
+pub fn format_json(report: &DayReport) -> String {
+    let (status, error, parse_nanos, parts) = match &report.outcome {
+        Ok(result) => (
+            if result.is_solved() { "ok" } else { "failed" },
+            "null".to_string(),
+            result.parse_duration.as_nanos().to_string(),
+            result
+                .parts
+                .iter()
+                .map(|part| {
+                    let (answer, error) = match &part.answer {
+                        Ok(answer) => (json_answer(answer), "null".to_string()),
+                        Err(error) => ("null".to_string(), json_string(&error.summary())),
+                    };
+                    format!(
+                        r#"{{"part":{},"answer":{},"error":{},"nanos":{}}}"#,
+                        part.part,
+                        answer,
+                        error,
+                        part.duration.as_nanos()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        Err(failure) => (
+            failure.status(),
+            json_string(&failure.to_string()),
+            "null".to_string(),
+            String::new(),
+        ),
+    };
+
+    format!(
+        r#"{{"day":{},"input":{},"status":"{}","error":{},"nanos":{},"parse_nanos":{},"parts":[{}]}}"#,
+        report.day,
+        json_string(&report.input),
+        status,
+        error,
+        report.duration.as_nanos(),
+        parse_nanos,
+        parts
+    )
+}
+
+/// All the days as a single JSON document, see the [module documentation](self) for the shape
+pub fn format_json_document(reports: &[DayReport], total: Duration) -> String {
+    format!(
+        r#"{{"days":[{}],"total_nanos":{}}}"#,
+        reports
+            .iter()
+            .map(format_json)
+            .collect::<Vec<_>>()
+            .join(","),
+        total.as_nanos()
+    )
+}
+
+/// Integer answers are output as JSON numbers, anything else as a string
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Integer(value) => value.to_string(),
+        Answer::Text(value) => json_string(value),
+    }
+}
+
+/// Quote and escape a string as a JSON string literal
+fn json_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for chr in value.chars() {
+        match chr {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::parser::parse_number;
+    use crate::report::*;
+    use crate::solution::PartResult;
+
+    fn example_report() -> DayReport {
+        DayReport {
+            day: 7,
+            input: "res/day-7-input.txt".to_string(),
+            outcome: Ok(DayResult {
+                parse_duration: Duration::from_nanos(300),
+                parts: vec![
+                    PartResult {
+                        part: 1,
+                        answer: Ok(Answer::Integer(6440)),
+                        duration: Duration::from_nanos(1200),
+                    },
+                    PartResult {
+                        part: 2,
+                        answer: Ok(Answer::Text("AB\"C".to_string())),
+                        duration: Duration::from_nanos(800),
+                    },
+                ],
+            }),
+            duration: Duration::from_nanos(2500),
+        }
+    }
+
+    /// The example with part 1 failing to be solved
+    fn unsolved_report() -> DayReport {
+        let mut report = example_report();
+        if let Ok(result) = &mut report.outcome {
+            result.parts[0].answer = Err(Error::solver("No route to ZZZ"));
+        }
+
+        report
+    }
+
+    /// The example with its input failing to parse
+    fn invalid_report() -> DayReport {
+        let input = "32T3K 765\nKK677 x";
+        let error = Error::from(parse_number::<u32>(&input[16..]).unwrap_err()).locate(input);
+
+        DayReport {
+            outcome: Err(Failure::InvalidInput {
+                summary: error.summary(),
+                description: error.describe(),
+            }),
+            ..example_report()
+        }
+    }
+
+    #[test]
+    fn can_parse_output_format() {
+        assert_eq!("text".parse(), Ok(OutputFormat::Text));
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert_eq!("jsonl".parse(), Ok(OutputFormat::JsonLines));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn can_format_json() {
+        assert_eq!(
+            format_json(&example_report()),
+            r#"{"day":7,"input":"res/day-7-input.txt","status":"ok","error":null,"nanos":2500,"parse_nanos":300,"parts":[{"part":1,"answer":6440,"error":null,"nanos":1200},{"part":2,"answer":"AB\"C","error":null,"nanos":800}]}"#
+        );
+
+        assert_eq!(
+            format_json(&unsolved_report()),
+            r#"{"day":7,"input":"res/day-7-input.txt","status":"failed","error":null,"nanos":2500,"parse_nanos":300,"parts":[{"part":1,"answer":null,"error":"No route to ZZZ","nanos":1200},{"part":2,"answer":"AB\"C","error":null,"nanos":800}]}"#
+        );
+
+        let failed = DayReport {
+            outcome: Err(Failure::Failed("Panicked: oops".to_string())),
+            ..example_report()
+        };
+        assert_eq!(
+            format_json(&failed),
+            r#"{"day":7,"input":"res/day-7-input.txt","status":"failed","error":"Panicked: oops","nanos":2500,"parse_nanos":null,"parts":[]}"#
+        );
+
+        let missing = DayReport {
+            outcome: Err(Failure::MissingInput("res/day-7-input.txt".to_string())),
+            ..example_report()
+        };
+        assert_eq!(
+            format_json(&missing),
+            r#"{"day":7,"input":"res/day-7-input.txt","status":"missing-input","error":"Missing input: res/day-7-input.txt","nanos":2500,"parse_nanos":null,"parts":[]}"#
+        );
+    }
+
+    #[test]
+    fn can_format_json_document() {
+        assert_eq!(
+            format_json_document(&[], Duration::from_micros(5)),
+            r#"{"days":[],"total_nanos":5000}"#
+        );
+    }
+
+    #[test]
+    fn can_escape_json_strings() {
+        assert_eq!(json_string("plain"), r#""plain""#);
+        assert_eq!(
+            json_string("quote\" back\\ line\n tab\t bell\u{7}"),
+            r#""quote\" back\\ line\n tab\t bell\u0007""#
+        );
+    }
+
+    #[test]
+    fn can_format_text() {
+        assert_eq!(
+            format_text(&example_report()),
+            "\
+Parsed input in 300.00ns
+Part 1: 6440 (in 1.20µs)
+Part 2: AB\"C (in 800.00ns)
+"
+        );
+        assert_eq!(
+            format_text(&unsolved_report()),
+            "\
+Parsed input in 300.00ns
+Part 1: FAILED: No route to ZZZ (in 1.20µs)
+Part 2: AB\"C (in 800.00ns)
+"
+        );
+        assert_eq!(
+            format_text(&invalid_report()),
+            "\
+FAILED: Invalid input
+Expected a number, found 'x'
+ --> line 2, column 7
+  |
+2 | KK677 x
+  |       ^
+"
+        );
+    }
+
+    #[test]
+    fn can_format_batch_table() {
+        let reports = vec![
+            example_report(),
+            DayReport {
+                input: "res/day-7/alice.txt".to_string(),
+                outcome: Ok(DayResult {
+                    parse_duration: Duration::from_nanos(300),
+                    parts: vec![PartResult {
+                        part: 2,
+                        answer: Ok(Answer::Integer(5905)),
+                        duration: Duration::from_nanos(800),
+                    }],
+                }),
+                ..example_report()
+            },
+            DayReport {
+                input: "res/day-7/carol.txt".to_string(),
+                ..unsolved_report()
+            },
+            DayReport {
+                input: "res/day-7/dave.txt".to_string(),
+                ..invalid_report()
+            },
+            DayReport {
+                input: "res/day-7/bob.txt".to_string(),
+                outcome: Err(Failure::Failed("Panicked: oops".to_string())),
+                ..example_report()
+            },
+        ];
+
+        assert_eq!(
+            format_batch_table(&reports),
+            "\
+| Input | Part 1 | Time | Part 2 | Time | Total |
+|:------|-------:|-----:|-------:|-----:|------:|
+| res/day-7-input.txt | 6440 | 1.20µs | AB\"C | 800.00ns | 2.50µs |
+| res/day-7/alice.txt | - | - | 5905 | 800.00ns | 2.50µs |
+| res/day-7/carol.txt | FAILED: No route to ZZZ | 1.20µs | AB\"C | 800.00ns | 2.50µs |
+| res/day-7/dave.txt | FAILED: Invalid input: Line 2, column 7: Expected a number, found 'x' | | | | 2.50µs |
+| res/day-7/bob.txt | FAILED: Panicked: oops | | | | 2.50µs |
+"
+        );
+    }
+
+    #[test]
+    fn can_format_summary() {
+        let reports = vec![
+            example_report(),
+            DayReport {
+                day: 8,
+                outcome: Err(Failure::Failed("Panicked: oops".to_string())),
+                ..example_report()
+            },
+            DayReport {
+                day: 11,
+                ..unsolved_report()
+            },
+            DayReport {
+                day: 9,
+                outcome: Err(Failure::MissingInput("res/day-9-input.txt".to_string())),
+                ..example_report()
+            },
+            DayReport {
+                day: 10,
+                outcome: Err(Failure::MissingInput("res/day-10-input.txt".to_string())),
+                ..example_report()
+            },
+        ];
+
+        assert_eq!(
+            format_summary(&reports),
+            "\
+Succeeded: 1 (days 7)
+Failed: 2 (days 8, 11)
+Missing input: 2 (days 9, 10)
+"
+        );
+        assert_eq!(
+            format_summary(&reports[..1]),
+            "Succeeded: 1 (days 7)\nFailed: none\nMissing input: none\n"
+        );
+    }
+}
@@ -0,0 +1,557 @@
+//! Runs the [`DaySolution`]s registered for a year in [`crate::YEARS`] against puzzle inputs, and reports their
+//! answers and timings.
+//!
+//! Puzzle inputs are read from `res/<year>/day-N-input.txt` by default, but a single day can be given any
+//! [`InputSource`], so that alternative inputs can be tried without overwriting the real one.
+
+use crate::answers::{
+    find_mismatches, load_answers, recorded_parts, save_answers, RecordedAnswers,
+};
+use crate::bench::{
+    bench_day, compare_to_baseline, default_baseline_path, format_comparisons, format_markdown,
+    format_text, load_baseline, save_baseline, BenchOptions,
+};
+use crate::error::{Result, ResultExt};
+use crate::report::{self, DayReport, Failure, OutputFormat};
+use crate::solution::{panic_message, DayResult, DaySolution, Registry};
+use crate::submit::{check_answer, load_submissions, submissions_path};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Where [`crate::bootstrap_day::bootstrap_day`] saves the puzzle input for a day
+pub fn default_input_path(year: u16, day: u8) -> String {
+    format!("res/{}/day-{}-input.txt", year, day)
+}
+
+/// Where to read the puzzle input from
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum InputSource {
+    File(String),
+    Stdin,
+    /// The input text itself, e.g. passed as a command line argument
+    Inline(String),
+}
+
+impl InputSource {
+    /// The real puzzle input for a day, see [`default_input_path`]
+    pub fn default_for(year: u16, day: u8) -> InputSource {
+        InputSource::File(default_input_path(year, day))
+    }
+
+    /// Read the puzzle input, distinguishing a file that hasn't been downloaded from other errors
+    fn read(&self) -> std::result::Result<String, Failure> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path).map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => Failure::MissingInput(path.to_string()),
+                _ => Failure::Failed(format!("Could not read {}: {}", path, e)),
+            }),
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|e| Failure::Failed(format!("Could not read stdin: {}", e)))?;
+                Ok(contents)
+            }
+            InputSource::Inline(contents) => Ok(contents.to_string()),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Inline(_) => write!(f, "<inline>"),
+        }
+    }
+}
+
+/// Where [`run_batch`] looks for alternative inputs for a day, unless another directory is provided
+pub fn default_batch_dir(year: u16, day: u8) -> String {
+    format!("res/{}/day-{}", year, day)
+}
+
+/// The parts to run given an optional single part requested by the user
+fn parts_to_run(part: Option<u8>) -> Vec<u8> {
+    part.map(|part| vec![part]).unwrap_or(vec![1, 2])
+}
+
+/// Run the solution, catching any panic while parsing so that one broken day doesn't stop the others from running.
+/// Panics in each part are caught by [`DaySolution::run`], keeping the other part's answer. Invalid input is reported
+/// with a diagnostic pointing at the problem.
+fn run_isolated(
+    solution: &dyn DaySolution,
+    input: &str,
+    parts: &[u8],
+) -> std::result::Result<DayResult, Failure> {
+    panic::catch_unwind(AssertUnwindSafe(|| solution.run(input, parts)))
+        .map_err(|payload| Failure::Failed(format!("Panicked: {}", panic_message(&*payload))))?
+        .map_err(|error| Failure::InvalidInput {
+            summary: error.summary(),
+            description: error.describe(),
+        })
+}
+
+/// Run a single day against the provided input, capturing the answers, or the reason it failed
+fn run_report(
+    day: u8,
+    solution: &dyn DaySolution,
+    input: &InputSource,
+    part: Option<u8>,
+) -> DayReport {
+    let start = Instant::now();
+    let outcome = input
+        .read()
+        .and_then(|contents| run_isolated(solution, &contents, &parts_to_run(part)));
+
+    DayReport {
+        day,
+        input: input.to_string(),
+        outcome,
+        duration: start.elapsed(),
+    }
+}
+
+/// Run a single day against the provided input, printing the answers in the requested format. Returns whether the
+/// day ran successfully and solved every requested part.
+pub fn run_day(
+    year: u16,
+    day: u8,
+    solution: &dyn DaySolution,
+    input: &InputSource,
+    part: Option<u8>,
+    format: OutputFormat,
+) -> bool {
+    let report = run_report(day, solution, input, part);
+
+    match format {
+        OutputFormat::Text => print!("{}", report::format_text(&report)),
+        OutputFormat::Json => println!(
+            "{}",
+            report::format_json_document(slice::from_ref(&report), report.duration)
+        ),
+        OutputFormat::JsonLines => println!("{}", report::format_json(&report)),
+    }
+
+    if *input == InputSource::default_for(year, day) {
+        warn_about_known_answers(year, &report);
+    }
+
+    report.succeeded()
+}
+
+/// Warn about any answers for a day's real input that its submissions ledger shows must be wrong. Warnings go to
+/// stderr, so machine-readable output is unaffected.
+fn warn_about_known_answers(year: u16, report: &DayReport) {
+    let Ok(result) = &report.outcome else {
+        return;
+    };
+
+    match load_submissions(Path::new(&submissions_path(year, report.day))) {
+        Ok(submissions) => {
+            for part in &result.parts {
+                let Ok(answer) = &part.answer else {
+                    continue;
+                };
+                if let Some(problem) = check_answer(&submissions, part.part, answer) {
+                    eprintln!(
+                        "Warning: day {} part {} is wrong, {}",
+                        report.day, part.part, problem
+                    );
+                }
+            }
+        }
+        Err(error) => eprintln!("Warning: {}", error.describe()),
+    }
+}
+
+/// Run a day against every `.txt` file in `dir`, in file name order, e.g. to compare several people's inputs. Text
+/// output is a table with a row per input. Returns whether the day ran successfully against every input.
+pub fn run_batch(
+    day: u8,
+    solution: &dyn DaySolution,
+    dir: &Path,
+    part: Option<u8>,
+    format: OutputFormat,
+) -> Result<bool> {
+    let start = Instant::now();
+
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect()
+        })
+        .chain_err(|| format!("Could not read inputs from {}", dir.display()))?;
+    paths.retain(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"));
+    paths.sort();
+
+    let reports: Vec<_> = paths
+        .into_iter()
+        .map(|path| {
+            let input = InputSource::File(path.to_string_lossy().to_string());
+            let report = run_report(day, solution, &input, part);
+            if format == OutputFormat::JsonLines {
+                println!("{}", report::format_json(&report));
+            }
+            report
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Text if reports.is_empty() => {
+            println!("No .txt inputs found in {}", dir.display())
+        }
+        OutputFormat::Text => print!("{}", report::format_batch_table(&reports)),
+        OutputFormat::Json => println!(
+            "{}",
+            report::format_json_document(&reports, start.elapsed())
+        ),
+        OutputFormat::JsonLines => {}
+    }
+
+    Ok(reports.iter().all(DayReport::succeeded))
+}
+
+/// Output a report for one of the days in a run of all days
+fn print_day_report(report: &DayReport, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => {
+            println!("==== Day {} ====", report.day);
+            print!("{}", report::format_text(report));
+            println!("-- took {:.2?}", report.duration);
+        }
+        OutputFormat::JsonLines => println!("{}", report::format_json(report)),
+        // The document is output once all the days have been run
+        OutputFormat::Json => {}
+    }
+}
+
+/// Run every registered day against their default inputs, sharing the days between a thread per available core.
+/// The reports are returned in the same order as the registry, regardless of the order the days complete.
+fn run_parallel(year: u16, registry: &Registry) -> Vec<DayReport> {
+    let threads = thread::available_parallelism()
+        .map(|threads| threads.get())
+        .unwrap_or(1)
+        .min(registry.len());
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(Vec::with_capacity(registry.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                while let Some(&(day, solution)) =
+                    registry.get(next.fetch_add(1, Ordering::Relaxed))
+                {
+                    let input = InputSource::default_for(year, day);
+                    let report = run_report(day, solution, &input, None);
+                    reports.lock().unwrap().push(report);
+                }
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort_by_key(|report| report.day);
+    reports
+}
+
+/// Run every day registered for a year, against their default inputs. Each day is isolated, so a missing input or a
+/// panic is reported, and the remaining days still run. Returns whether every day ran successfully.
+///
+/// Days are run in order, outputting each as it completes, unless `parallel` is set. In that case the days are run
+/// concurrently, and output in day order once they have all completed.
+pub fn run_all(year: u16, registry: &Registry, format: OutputFormat, parallel: bool) -> bool {
+    let start = Instant::now();
+
+    let reports = if parallel {
+        let reports = run_parallel(year, registry);
+        for report in &reports {
+            print_day_report(report, format);
+            warn_about_known_answers(year, report);
+        }
+        reports
+    } else {
+        registry
+            .iter()
+            .map(|&(day, solution)| {
+                let report = run_report(day, solution, &InputSource::default_for(year, day), None);
+                print_day_report(&report, format);
+                warn_about_known_answers(year, &report);
+                report
+            })
+            .collect()
+    };
+
+    let wall_clock = start.elapsed();
+
+    if format == OutputFormat::Text {
+        println!();
+        print!("{}", report::format_summary(&reports));
+
+        if parallel {
+            let summed: Duration = reports.iter().map(|report| report.duration).sum();
+            println!(
+                "Wall-clock {:.2?}, summed time of each day {:.2?}",
+                wall_clock, summed
+            );
+        }
+    }
+
+    if format == OutputFormat::Json {
+        println!("{}", report::format_json_document(&reports, wall_clock));
+    }
+
+    reports.iter().all(DayReport::succeeded)
+}
+
+/// Run a day against its real input, and record the answers as confirmed correct in the answers file at `path`. Any
+/// parts that were solved are recorded even if another part fails, in which case the failure is returned.
+pub fn record_day(
+    year: u16,
+    day: u8,
+    solution: &dyn DaySolution,
+    part: Option<u8>,
+    path: &Path,
+) -> Result<()> {
+    let input_path = default_input_path(year, day);
+    let contents =
+        fs::read_to_string(&input_path).chain_err(|| format!("Could not read {}", input_path))?;
+    let result = solution.run(&contents, &parts_to_run(part))?;
+
+    let mut answers = load_answers(path)?;
+    let mut failures = Vec::new();
+    for part in result.parts {
+        match part.answer {
+            Ok(answer) => {
+                println!("Recording day {} part {}: {}", day, part.part, answer);
+                answers.insert((day, part.part), answer);
+            }
+            Err(error) => failures.push(format!("part {}: {}", part.part, error.summary())),
+        }
+    }
+
+    save_answers(path, &answers)?;
+    if !failures.is_empty() {
+        return Err(format!("Could not record day {} {}", day, failures.join(", ")).into());
+    }
+
+    Ok(())
+}
+
+/// Re-run every day registered for a year that has recorded answers, and report any that no longer match. Returns
+/// whether all the recorded answers were reproduced.
+pub fn verify(year: u16, registry: &Registry, answers: &RecordedAnswers) -> bool {
+    let mut failures = 0;
+
+    for &(day, solution) in registry {
+        let parts = recorded_parts(answers, day);
+        if parts.is_empty() {
+            println!("Day {}: no recorded answers", day);
+            continue;
+        }
+
+        let outcome = InputSource::default_for(year, day)
+            .read()
+            .and_then(|contents| run_isolated(solution, &contents, &parts));
+        let result = match outcome {
+            Ok(result) => result,
+            Err(failure) => {
+                println!("Day {}: FAILED - {}", day, failure);
+                failures += 1;
+                continue;
+            }
+        };
+        let mut solved = Vec::new();
+        let mut unsolved = 0;
+        for part in result.parts {
+            match part.answer {
+                Ok(answer) => solved.push((part.part, answer)),
+                Err(error) => {
+                    println!(
+                        "Day {} part {}: FAILED - {}",
+                        day,
+                        part.part,
+                        error.summary()
+                    );
+                    unsolved += 1;
+                }
+            }
+        }
+        let mismatches = find_mismatches(answers, day, solved);
+
+        if unsolved == 0 && mismatches.is_empty() {
+            println!("Day {}: ok", day);
+        } else {
+            for mismatch in mismatches {
+                println!(
+                    "Day {} part {}: FAILED - expected {}, got {}",
+                    day, mismatch.part, mismatch.expected, mismatch.actual
+                );
+            }
+            failures += 1;
+        }
+    }
+
+    println!();
+    if failures > 0 {
+        println!("{} day(s) failed verification", failures);
+    } else {
+        println!("All recorded answers verified");
+    }
+
+    failures == 0
+}
+
+/// Benchmark each of the given days from a year against their real input, then report the timings. If requested the
+/// results are compared to, and/or saved as, the baseline. Returns whether no steps regressed compared to the baseline.
+pub fn bench(year: u16, registry: &Registry, days: &[u8], options: &BenchOptions) -> Result<bool> {
+    let benchmarks: Vec<_> = registry
+        .iter()
+        .filter(|(day, _)| days.contains(day))
+        .filter_map(|&(day, solution)| {
+            let input_path = default_input_path(year, day);
+            let Ok(contents) = fs::read_to_string(&input_path) else {
+                eprintln!("Skipping day {}, could not read {}", day, input_path);
+                return None;
+            };
+
+            bench_day(day, solution, &contents, options)
+                .map_err(|error| eprintln!("Skipping day {}: {}", day, error.describe()))
+                .ok()
+        })
+        .collect();
+
+    if options.markdown {
+        print!("{}", format_markdown(&benchmarks))
+    } else {
+        print!("{}", format_text(&benchmarks))
+    }
+
+    let baseline_path = options
+        .baseline_path
+        .clone()
+        .unwrap_or_else(|| default_baseline_path(year));
+    let mut regressions = 0;
+
+    if options.compare {
+        let comparisons = compare_to_baseline(
+            &benchmarks,
+            &load_baseline(Path::new(&baseline_path))?,
+            options.threshold_percent,
+        );
+        regressions = comparisons.iter().filter(|c| c.regressed).count();
+
+        println!();
+        println!("==== Compared to {} ====", baseline_path);
+        print!("{}", format_comparisons(&comparisons));
+        if regressions > 0 {
+            println!(
+                "{} step(s) slower than the baseline by more than {}%",
+                regressions, options.threshold_percent
+            );
+        }
+    }
+
+    if options.save_baseline {
+        save_baseline(Path::new(&baseline_path), &benchmarks)?;
+        println!("Baseline saved to {}", baseline_path);
+    }
+
+    Ok(regressions == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::*;
+    use crate::solution::{Answer, Solution};
+
+    struct Panics;
+
+    impl Solution for Panics {
+        type Parsed<'a> = &'a str;
+
+        fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>> {
+            match input {
+                "" => panic!("Nothing to parse"),
+                input => Ok(input),
+            }
+        }
+
+        fn part_1(&self, parsed: &Self::Parsed<'_>) -> Result<Answer> {
+            Ok(parsed.len().into())
+        }
+
+        fn part_2(&self, parsed: &Self::Parsed<'_>) -> Result<Answer> {
+            panic!("No solution for {}", parsed)
+        }
+    }
+
+    #[test]
+    fn can_isolate_panics() {
+        let result = run_isolated(&Panics, "input", &[1]).unwrap();
+        assert_eq!(
+            result.parts[0].answer.as_ref().unwrap(),
+            &Answer::Integer(5)
+        );
+
+        let result = run_isolated(&Panics, "input", &[1, 2]).unwrap();
+        assert_eq!(
+            result.parts[0].answer.as_ref().unwrap(),
+            &Answer::Integer(5)
+        );
+        assert_eq!(
+            result.parts[1].answer.as_ref().unwrap_err().describe(),
+            "Panicked: No solution for input"
+        );
+
+        assert_eq!(
+            run_isolated(&Panics, "", &[1, 2]).unwrap_err(),
+            Failure::Failed("Panicked: Nothing to parse".to_string())
+        );
+    }
+
+    static PARALLEL_REGISTRY: &Registry = &[(21, &Panics), (22, &Panics), (23, &Panics)];
+
+    #[test]
+    fn can_run_in_parallel_in_day_order() {
+        let reports = run_parallel(2023, PARALLEL_REGISTRY);
+
+        assert_eq!(
+            reports.iter().map(|report| report.day).collect::<Vec<_>>(),
+            vec![21, 22, 23]
+        );
+    }
+
+    #[test]
+    fn can_run_inline_input() {
+        let input = InputSource::Inline("abc".to_string());
+        let report = run_report(1, &Panics, &input, Some(1));
+
+        assert_eq!(report.input, "<inline>");
+        assert_eq!(
+            report.outcome.unwrap().parts[0].answer.as_ref().unwrap(),
+            &Answer::Integer(3)
+        );
+    }
+
+    #[test]
+    fn can_report_missing_input() {
+        let input = InputSource::File("res/does-not-exist.txt".to_string());
+        let report = run_report(1, &Panics, &input, None);
+
+        assert_eq!(
+            report.outcome.unwrap_err(),
+            Failure::MissingInput("res/does-not-exist.txt".to_string())
+        );
+    }
+}
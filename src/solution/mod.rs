@@ -0,0 +1,330 @@
+//! Each day implements [`Solution`], splitting the work into parsing the puzzle input once, and then solving each
+//! part from that parsed representation. Parts return an [`Answer`] so that the results can be compared and stored
+//! regardless of the day.
+//!
+//! [`DaySolution`] erases the day specific types so that every day can be held in a single [`Registry`], and times each
+//! step as it runs them. Parsing or solving can fail with an [`Error`]. Parse errors are located within the input
+//! before being returned, whereas each part's result is kept separately, so that one part failing doesn't hide the
+//! other's answer.
+
+use crate::error::{Error, Result};
+use std::any::Any;
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// The answer to one part of a puzzle. Puzzle answers are almost always numbers, but occasionally text. Integers too
+/// large for an `i64` are kept as text, which is how they are also read back, see [`Answer::from_str`].
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// The inverse of [`Answer::fmt`], anything that is a valid integer is treated as such, otherwise it is text.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(s.parse()
+            .map(Answer::Integer)
+            .unwrap_or_else(|_| Answer::Text(s.to_string())))
+    }
+}
+
+/// Implement `From` for integer types that can always be represented as an `i64`
+macro_rules! answer_from_integer {
+    ($($int:ty),*) => {
+        $(impl From<$int> for Answer {
+            fn from(value: $int) -> Self {
+                Answer::Integer(value.into())
+            }
+        })*
+    };
+}
+
+answer_from_integer!(i8, i16, i32, i64, u8, u16, u32);
+
+/// Implement `From` for integer types that might be too large for an `i64`, falling back to text for those values
+macro_rules! answer_from_large_integer {
+    ($($int:ty),*) => {
+        $(impl From<$int> for Answer {
+            fn from(value: $int) -> Self {
+                value
+                    .try_into()
+                    .map(Answer::Integer)
+                    .unwrap_or_else(|_| Answer::Text(value.to_string()))
+            }
+        })*
+    };
+}
+
+answer_from_large_integer!(usize, u64, i128, u128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// A day's puzzle solution
+pub trait Solution {
+    /// The internal representation of the puzzle input. This may borrow from the input text.
+    type Parsed<'a>;
+
+    /// Turn the puzzle input into the internal representation shared by both parts. Parse errors should be created
+    /// from slices of `input` so that they can be located, see [`crate::parser`].
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>>;
+
+    /// Solve part 1 from the parsed input
+    fn part_1(&self, parsed: &Self::Parsed<'_>) -> Result<Answer>;
+
+    /// Solve part 2 from the parsed input
+    fn part_2(&self, parsed: &Self::Parsed<'_>) -> Result<Answer>;
+}
+
+/// The answer to one part of a day, or why it couldn't be solved, and how long it took to calculate
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Result<Answer>,
+    pub duration: Duration,
+}
+
+/// The results of running the requested parts of a day
+#[derive(Debug)]
+pub struct DayResult {
+    pub parse_duration: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    /// Whether every requested part was solved
+    pub fn is_solved(&self) -> bool {
+        self.parts.iter().all(|part| part.answer.is_ok())
+    }
+}
+
+/// An object-safe view of a [`Solution`], implemented for all of them
+pub trait DaySolution: Sync {
+    /// Parse the input, then solve each of the requested parts, timing each step separately. Only failing to parse is
+    /// an error, each part's failure, including panicking, is recorded in its [`PartResult`].
+    fn run(&self, input: &str, parts: &[u8]) -> Result<DayResult>;
+}
+
+impl<S: Solution + Sync> DaySolution for S {
+    fn run(&self, input: &str, parts: &[u8]) -> Result<DayResult> {
+        let start = Instant::now();
+        let parsed = self.parse(input).map_err(|error| error.locate(input))?;
+        let parse_duration = start.elapsed();
+
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let answer = panic::catch_unwind(AssertUnwindSafe(|| match part {
+                    1 => self.part_1(&parsed),
+                    2 => self.part_2(&parsed),
+                    _ => unreachable!("Invalid part {}", part),
+                }))
+                .unwrap_or_else(|payload| {
+                    Err(Error::solver(format!(
+                        "Panicked: {}",
+                        panic_message(&*payload)
+                    )))
+                });
+
+                PartResult {
+                    part,
+                    answer,
+                    duration: start.elapsed(),
+                }
+            })
+            .collect();
+
+        Ok(DayResult {
+            parse_duration,
+            parts,
+        })
+    }
+}
+
+/// Panics are usually raised with a `&str` or formatted `String` message, anything else can't be described
+pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
+
+/// Each available [`DaySolution`], keyed by day
+pub type Registry = [(u8, &'static dyn DaySolution)];
+
+/// Find the solution for a specific day, if it has been registered
+pub fn find_solution(registry: &Registry, day: u8) -> Option<&'static dyn DaySolution> {
+    registry
+        .iter()
+        .find(|(registered_day, _)| *registered_day == day)
+        .map(|&(_, solution)| solution)
+}
+
+/// Each event's [`Registry`], keyed by year
+pub type Years = [(u16, &'static Registry)];
+
+/// Find the solutions for a specific year, if any have been registered
+pub fn find_year(years: &Years, year: u16) -> Option<&'static Registry> {
+    years
+        .iter()
+        .find(|(registered_year, _)| *registered_year == year)
+        .map(|&(_, registry)| registry)
+}
+
+/// The most recent year with registered solutions, used when a command doesn't specify one
+pub fn latest_year(years: &Years) -> Option<u16> {
+    years.iter().map(|&(year, _)| year).max()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::parser::ParseError;
+    use crate::solution::*;
+
+    struct Example;
+
+    impl Solution for Example {
+        type Parsed<'a> = Vec<&'a str>;
+
+        fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>> {
+            input
+                .split(',')
+                .map(|item| match item.trim() {
+                    "" => Err(ParseError::new(item, "Empty item").into()),
+                    item => Ok(item),
+                })
+                .collect()
+        }
+
+        fn part_1(&self, parsed: &Self::Parsed<'_>) -> Result<Answer> {
+            Ok(parsed.len().into())
+        }
+
+        fn part_2(&self, parsed: &Self::Parsed<'_>) -> Result<Answer> {
+            match parsed.len() {
+                1 => Err(Error::solver("Need more than one item")),
+                _ => Ok(parsed.join("").into()),
+            }
+        }
+    }
+
+    static EXAMPLE_REGISTRY: &Registry = &[(3, &Example)];
+    static EXAMPLE_YEARS: &Years = &[(2022, EXAMPLE_REGISTRY), (2023, &[])];
+
+    #[test]
+    fn can_run_requested_parts() {
+        let answers = |parts: &[u8]| -> Vec<(u8, Answer)> {
+            Example
+                .run("a,b,c", parts)
+                .unwrap()
+                .parts
+                .into_iter()
+                .map(|result| (result.part, result.answer.unwrap()))
+                .collect()
+        };
+
+        assert_eq!(
+            answers(&[1, 2]),
+            vec![
+                (1, Answer::Integer(3)),
+                (2, Answer::Text("abc".to_string()))
+            ]
+        );
+        assert_eq!(answers(&[2]), vec![(2, Answer::Text("abc".to_string()))]);
+    }
+
+    #[test]
+    fn can_locate_parse_errors() {
+        let error = Example.run("a,b\nc,,d", &[1]).unwrap_err();
+
+        assert_eq!(error.to_string(), "Line 2, column 3: Empty item");
+    }
+
+    #[test]
+    fn can_report_solver_errors() {
+        assert!(Example.run("a", &[1]).unwrap().is_solved());
+
+        let result = Example.run("a", &[1, 2]).unwrap();
+        assert!(!result.is_solved());
+        assert_eq!(
+            result.parts[0].answer.as_ref().unwrap(),
+            &Answer::Integer(1)
+        );
+        assert_eq!(
+            result.parts[1].answer.as_ref().unwrap_err().describe(),
+            "Need more than one item"
+        );
+    }
+
+    #[test]
+    fn can_round_trip_answers() {
+        let examples = vec![
+            ("142", Answer::Integer(142)),
+            ("-3", Answer::Integer(-3)),
+            ("ABCDE", Answer::Text("ABCDE".to_string())),
+            ("1,2,3", Answer::Text("1,2,3".to_string())),
+        ];
+
+        for (text, answer) in examples {
+            assert_eq!(text.parse::<Answer>(), Ok(answer.clone()));
+            assert_eq!(answer.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn can_convert_integers_to_answers() {
+        assert_eq!(Answer::from(281u32), Answer::Integer(281));
+        assert_eq!(Answer::from(-5i32), Answer::Integer(-5));
+        assert_eq!(Answer::from(6440usize), Answer::Integer(6440));
+
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::Text("18446744073709551615".to_string())
+        );
+        assert_eq!(
+            Answer::from(u64::MAX).to_string().parse(),
+            Ok(Answer::from(u64::MAX))
+        );
+        assert_eq!(Answer::from(-7i128), Answer::Integer(-7));
+    }
+
+    #[test]
+    fn can_find_solution() {
+        assert!(find_solution(EXAMPLE_REGISTRY, 3).is_some());
+        assert!(find_solution(EXAMPLE_REGISTRY, 4).is_none());
+    }
+
+    #[test]
+    fn can_find_year() {
+        assert_eq!(find_year(EXAMPLE_YEARS, 2022).map(<[_]>::len), Some(1));
+        assert!(find_year(EXAMPLE_YEARS, 2021).is_none());
+        assert_eq!(latest_year(EXAMPLE_YEARS), Some(2023));
+    }
+}
@@ -0,0 +1,467 @@
+//! Submits a day's answers to the Advent of Code website, and keeps a ledger of what has been submitted.
+//!
+//! Each day's submissions are kept next to its input, in [`submissions_path`], one per line in the format
+//! `<part> <outcome> <answer>`. The outcome is one of `correct`, `too-high`, `too-low`, or `wrong`. Answers tried
+//! directly on the website can be added with [`record_submission`]. An answer that is already in the ledger isn't
+//! submitted again, nor is one that [`check_answer`] shows must be wrong. Correct answers are also recorded in
+//! [`crate::answers::answers_path`] so that [`crate::runner::verify`] can check them.
+
+use crate::answers::{answers_path, load_answers, save_answers};
+use crate::client::AocClient;
+use crate::error::{Result, ResultExt};
+use crate::runner::default_input_path;
+use crate::solution::{Answer, DaySolution};
+use regex::Regex;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// The website's response to a submitted answer
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Incorrect, without a hint as to which way
+    Wrong,
+    /// Another answer was submitted too recently, so this one wasn't checked
+    RateLimited {
+        wait: String,
+    },
+    /// The part has already been solved, so this answer wasn't checked
+    AlreadyComplete,
+}
+
+impl Outcome {
+    /// Whether the website checked the answer, rather than refusing to
+    pub fn is_judgement(&self) -> bool {
+        !matches!(self, Outcome::RateLimited { .. } | Outcome::AlreadyComplete)
+    }
+
+    /// How the outcome is stored in the ledger. Only judgements are recorded, so only they can be parsed back.
+    fn key(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited { .. } => "rate-limited",
+            Outcome::AlreadyComplete => "already-complete",
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    /// The inverse of `Outcome::key` for judgements
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "wrong" => Ok(Outcome::Wrong),
+            _ => Err(format!("Unknown outcome '{}'", s)),
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Outcome::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Outcome::Wrong => write!(f, "That's not the right answer"),
+            Outcome::RateLimited { wait } => {
+                write!(f, "An answer was submitted too recently, wait {}", wait)
+            }
+            Outcome::AlreadyComplete => write!(f, "This part has already been completed"),
+        }
+    }
+}
+
+/// An answer that has been checked by the website
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Submission {
+    pub part: u8,
+    pub outcome: Outcome,
+    pub answer: Answer,
+}
+
+/// Where a day's submissions are recorded, alongside its input
+pub fn submissions_path(year: u16, day: u8) -> String {
+    format!("res/{}/day-{}-submissions.txt", year, day)
+}
+
+/// Run a day against its real input in the project at `root`, and submit the answer for `part` unless it has been
+/// submitted before. Judgements are recorded in the ledger, see the [module documentation](self).
+pub fn submit_day(
+    client: &dyn AocClient,
+    root: &Path,
+    year: u16,
+    day: u8,
+    solution: &dyn DaySolution,
+    part: u8,
+) -> Result<Outcome> {
+    let input_path = root.join(default_input_path(year, day));
+    let input = fs::read_to_string(&input_path)
+        .chain_err(|| format!("Could not read {}", input_path.display()))?;
+    let answer = solution
+        .run(&input, &[part])?
+        .parts
+        .into_iter()
+        .find(|result| result.part == part)
+        .map(|result| result.answer)
+        .ok_or_else(|| format!("Day {} has no answer for part {}", day, part))??;
+
+    let ledger_path = root.join(submissions_path(year, day));
+    let submissions = load_submissions(&ledger_path)?;
+
+    if let Some(previous) = submissions
+        .iter()
+        .find(|submission| submission.part == part && submission.answer == answer)
+    {
+        println!(
+            "{} has already been submitted for day {} part {}",
+            answer, day, part
+        );
+        return Ok(previous.outcome.clone());
+    }
+
+    if submissions
+        .iter()
+        .any(|submission| submission.part == part && submission.outcome == Outcome::Correct)
+    {
+        println!(
+            "Day {} part {} has already been answered correctly, not submitting {}",
+            day, part, answer
+        );
+        return Ok(Outcome::AlreadyComplete);
+    }
+
+    if let Some(problem) = check_answer(&submissions, part, &answer) {
+        return Err(format!(
+            "Not submitting {} for day {} part {}, {}",
+            answer, day, part, problem
+        )
+        .into());
+    }
+
+    println!("Submitting {} for day {} part {}", answer, day, part);
+    let outcome = parse_response(&client.submit_answer(year, day, part, &answer)?)?;
+
+    if outcome.is_judgement() {
+        let submission = Submission {
+            part,
+            outcome: outcome.clone(),
+            answer: answer.clone(),
+        };
+        record_submission(&ledger_path, &submission).chain_err(|| {
+            format!(
+                "Failed to record the submission in {}",
+                ledger_path.display()
+            )
+        })?;
+    }
+
+    if outcome == Outcome::Correct {
+        let answers_path = root.join(answers_path(year));
+        let mut answers = load_answers(&answers_path)?;
+        answers.insert((day, part), answer);
+        save_answers(&answers_path, &answers)?;
+    }
+
+    Ok(outcome)
+}
+
+/// Describe why `answer` is known to be wrong for `part`, either because it has been submitted and rejected before,
+/// because a different answer was correct, or because it is outside the bounds set by earlier answers that were too
+/// high or too low.
+pub fn check_answer(submissions: &[Submission], part: u8, answer: &Answer) -> Option<String> {
+    let submissions: Vec<_> = submissions
+        .iter()
+        .filter(|submission| submission.part == part)
+        .collect();
+
+    if let Some(previous) = submissions
+        .iter()
+        .find(|submission| &submission.answer == answer && submission.outcome != Outcome::Correct)
+    {
+        return Some(format!(
+            "{} has already been tried: {}",
+            answer, previous.outcome
+        ));
+    }
+
+    if let Some(correct) = submissions
+        .iter()
+        .find(|submission| submission.outcome == Outcome::Correct && &submission.answer != answer)
+    {
+        return Some(format!(
+            "{} doesn't match {}, which was the right answer",
+            answer, correct.answer
+        ));
+    }
+
+    let Answer::Integer(value) = answer else {
+        return None;
+    };
+    let bound = |outcome: Outcome| {
+        submissions
+            .iter()
+            .filter(move |submission| submission.outcome == outcome)
+            .filter_map(|submission| match submission.answer {
+                Answer::Integer(bound) => Some(bound),
+                Answer::Text(_) => None,
+            })
+    };
+
+    if let Some(too_high) = bound(Outcome::TooHigh)
+        .min()
+        .filter(|&too_high| *value > too_high)
+    {
+        return Some(format!(
+            "{} is higher than {}, which was too high",
+            value, too_high
+        ));
+    }
+
+    if let Some(too_low) = bound(Outcome::TooLow)
+        .max()
+        .filter(|&too_low| *value < too_low)
+    {
+        return Some(format!(
+            "{} is lower than {}, which was too low",
+            value, too_low
+        ));
+    }
+
+    None
+}
+
+/// Work out the outcome from the page the website responds to a submission with
+pub fn parse_response(page: &str) -> Result<Outcome> {
+    let text = article_text(page);
+
+    if text.contains("That's the right answer") {
+        Ok(Outcome::Correct)
+    } else if text.contains("You gave an answer too recently") {
+        let wait = Regex::new(r"You have (.+?) left to wait")
+            .unwrap()
+            .captures(&text)
+            .map_or("a while".to_string(), |captures| captures[1].to_string());
+        Ok(Outcome::RateLimited { wait })
+    } else if text.contains("You don't seem to be solving the right level") {
+        Ok(Outcome::AlreadyComplete)
+    } else if text.contains("That's not the right answer") {
+        Ok(if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        })
+    } else {
+        Err(format!("Unrecognised response from the website: '{}'", text).into())
+    }
+}
+
+/// The text of the page's `<article>`, which holds the response message, with any markup removed
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    let text = Regex::new(r"<[^>]*>").unwrap().replace_all(article, "");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Load a day's submissions, treating a missing file as nothing having been submitted yet
+pub fn load_submissions(path: &Path) -> Result<Vec<Submission>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(parse_submissions(&contents)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Add a submission to the end of a day's ledger, creating it, and the year's directory, if needed
+pub fn record_submission(path: &Path, submission: &Submission) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(format_submission(submission).as_bytes())?;
+
+    Ok(())
+}
+
+/// Parse the contents of a ledger, see the [module documentation](self) for the format
+fn parse_submissions(contents: &str) -> std::result::Result<Vec<Submission>, String> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_number, line)| {
+            parse_submission_line(line).ok_or(format!(
+                "Invalid submission on line {}: '{}'",
+                line_number + 1,
+                line
+            ))
+        })
+        .collect()
+}
+
+/// Parse a line in the format `<part> <outcome> <answer>`. The answer is the rest of the line, so may contain spaces.
+fn parse_submission_line(line: &str) -> Option<Submission> {
+    let mut fields = line.splitn(3, ' ');
+    let part = fields.next()?.parse().ok()?;
+    let outcome = fields.next()?.parse().ok()?;
+    let answer = fields.next()?.parse().ok()?;
+
+    Some(Submission {
+        part,
+        outcome,
+        answer,
+    })
+}
+
+fn format_submission(submission: &Submission) -> String {
+    format!(
+        "{} {} {}\n",
+        submission.part,
+        submission.outcome.key(),
+        submission.answer
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::submit::*;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en-us\">\n<body>\n<main>\n<article><p>{}</p></article>\n</main>\n</body>\n</html>",
+            message
+        )
+    }
+
+    #[test]
+    fn can_parse_response() {
+        assert_eq!(
+            parse_response(&page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.")).unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href=\"/2023/day/5\">[Return to Day 5]</a>")).unwrap(),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+            .unwrap(),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer.  If you're stuck, make sure you're using the full input data.")).unwrap(),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 35s left to wait.")).unwrap(),
+            Outcome::RateLimited { wait: "4m 35s".to_string() }
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+            .unwrap(),
+            Outcome::AlreadyComplete
+        );
+        assert_eq!(
+            parse_response(&page("Something <em>else</em>"))
+                .unwrap_err()
+                .describe(),
+            "Unrecognised response from the website: 'Something else'"
+        );
+    }
+
+    #[test]
+    fn can_check_answers() {
+        let submission = |part: u8, outcome: Outcome, answer: i64| Submission {
+            part,
+            outcome,
+            answer: Answer::Integer(answer),
+        };
+        let submissions = vec![
+            submission(1, Outcome::TooHigh, 500),
+            submission(1, Outcome::TooLow, 100),
+            submission(1, Outcome::TooHigh, 400),
+            submission(1, Outcome::Wrong, 250),
+            submission(2, Outcome::Correct, 42),
+        ];
+        let check =
+            |part: u8, answer: i64| check_answer(&submissions, part, &Answer::Integer(answer));
+
+        assert_eq!(check(1, 300), None);
+        assert_eq!(
+            check(1, 250),
+            Some("250 has already been tried: That's not the right answer".to_string())
+        );
+        assert_eq!(
+            check(1, 400),
+            Some(
+                "400 has already been tried: That's not the right answer, it's too high"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check(1, 450),
+            Some("450 is higher than 400, which was too high".to_string())
+        );
+        assert_eq!(
+            check(1, 99),
+            Some("99 is lower than 100, which was too low".to_string())
+        );
+        assert_eq!(check(2, 42), None);
+        assert_eq!(
+            check(2, 1000),
+            Some("1000 doesn't match 42, which was the right answer".to_string())
+        );
+        assert_eq!(check_answer(&submissions, 1, &"ABC".into()), None);
+    }
+
+    #[test]
+    fn can_parse_and_format_submissions() {
+        let submissions = vec![
+            Submission {
+                part: 1,
+                outcome: Outcome::TooHigh,
+                answer: Answer::Integer(35),
+            },
+            Submission {
+                part: 1,
+                outcome: Outcome::Correct,
+                answer: Answer::Integer(31),
+            },
+            Submission {
+                part: 2,
+                outcome: Outcome::Wrong,
+                answer: Answer::Text("ABC DEF".to_string()),
+            },
+        ];
+
+        let formatted: String = submissions.iter().map(format_submission).collect();
+        assert_eq!(formatted, "1 too-high 35\n1 correct 31\n2 wrong ABC DEF\n");
+        assert_eq!(parse_submissions(&formatted), Ok(submissions));
+        assert_eq!(
+            parse_submissions("1 too-high 35\n1 maybe 31"),
+            Err("Invalid submission on line 2: '1 maybe 31'".to_string())
+        );
+    }
+}
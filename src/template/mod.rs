@@ -0,0 +1,182 @@
+//! Renders the templates that [`crate::bootstrap_day`] generates a day's files from, so the scaffold can be changed
+//! without editing the code.
+//!
+//! A template is plain text with `{{name}}` placeholders:
+//!
+//! - `{{year}}`, `{{day}}` and `{{title}}` are replaced with the puzzle's details.
+//! - `{{example_number}}`, `{{example_path}}` and `{{example_input}}` are the number of an example from the puzzle
+//!   description counting from 1, the path it was saved to relative to the project root, and its text. A line using
+//!   any of these is repeated for each example, or left out if there are none.
+//! - Lines between `{{#examples}}` and `{{/examples}}`, each on a line of their own, are only included if there is at
+//!   least one example.
+
+use regex::{Captures, Regex};
+
+/// Matches a `{{name}}` placeholder, capturing the name
+const PLACEHOLDER_PATTERN: &str = r"\{\{(\w+)\}\}";
+
+/// An example input from a puzzle description
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Example {
+    /// Where the example was saved, relative to the project root
+    pub path: String,
+    pub input: String,
+}
+
+/// The values substituted into a template
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Placeholders {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    pub examples: Vec<Example>,
+}
+
+/// Render `template` with the given values, failing if it uses a placeholder that doesn't exist, or has an unclosed
+/// `{{#examples}}` section
+pub fn render(template: &str, placeholders: &Placeholders) -> Result<String, String> {
+    let pattern = Regex::new(PLACEHOLDER_PATTERN).unwrap();
+    let mut rendered = String::new();
+    let mut in_section = false;
+
+    for line in template.split_inclusive('\n') {
+        match line.trim() {
+            "{{#examples}}" if in_section => {
+                return Err("{{#examples}} sections can't be nested".to_string())
+            }
+            "{{#examples}}" => in_section = true,
+            "{{/examples}}" if !in_section => {
+                return Err("{{/examples}} doesn't close an {{#examples}} section".to_string())
+            }
+            "{{/examples}}" => in_section = false,
+            _ if in_section && placeholders.examples.is_empty() => {}
+            _ if uses_example(&pattern, line) => {
+                for (index, example) in placeholders.examples.iter().enumerate() {
+                    rendered +=
+                        &substitute(&pattern, line, placeholders, Some((index + 1, example)))?;
+                }
+            }
+            _ => rendered += &substitute(&pattern, line, placeholders, None)?,
+        }
+    }
+
+    if in_section {
+        return Err("{{#examples}} section is never closed".to_string());
+    }
+
+    Ok(rendered)
+}
+
+/// Whether a line includes any of the placeholders that are specific to an example
+fn uses_example(pattern: &Regex, line: &str) -> bool {
+    pattern
+        .captures_iter(line)
+        .any(|captures| captures[1].starts_with("example_"))
+}
+
+/// Replace the placeholders in a single line, `example` being the one to use for any example placeholders
+fn substitute(
+    pattern: &Regex,
+    line: &str,
+    placeholders: &Placeholders,
+    example: Option<(usize, &Example)>,
+) -> Result<String, String> {
+    let mut error = None;
+    let substituted = pattern.replace_all(line, |captures: &Captures| {
+        let value = match (&captures[1], example) {
+            ("year", _) => Some(placeholders.year.to_string()),
+            ("day", _) => Some(placeholders.day.to_string()),
+            ("title", _) => Some(placeholders.title.clone()),
+            ("example_number", Some((number, _))) => Some(number.to_string()),
+            ("example_path", Some((_, example))) => Some(example.path.clone()),
+            ("example_input", Some((_, example))) => Some(example.input.clone()),
+            _ => None,
+        };
+
+        value.unwrap_or_else(|| {
+            error.get_or_insert_with(|| format!("Unknown placeholder '{}'", &captures[0]));
+            String::new()
+        })
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(substituted.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::template::*;
+
+    fn placeholders(example_count: usize) -> Placeholders {
+        Placeholders {
+            year: 2023,
+            day: 4,
+            title: "Scratchcards".to_string(),
+            examples: (1..=example_count)
+                .map(|number| Example {
+                    path: format!("res/2023/day-4-example-{}.txt", number),
+                    input: format!("Card {}", number),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn can_render_placeholders() {
+        assert_eq!(
+            render("Day {{day}}: {{title}} ({{year}})\n", &placeholders(0)),
+            Ok("Day 4: Scratchcards (2023)\n".to_string())
+        );
+        assert_eq!(
+            render("{{day}}{{day}}", &placeholders(0)),
+            Ok("44".to_string())
+        );
+    }
+
+    #[test]
+    fn can_repeat_example_lines() {
+        let template = "\
+start
+{{#examples}}
+{{example_number}}: {{example_path}} for day {{day}}
+'{{example_input}}'
+{{/examples}}
+end
+";
+
+        assert_eq!(
+            render(template, &placeholders(0)),
+            Ok("start\nend\n".to_string())
+        );
+        assert_eq!(
+            render(template, &placeholders(2)),
+            Ok("\
+start
+1: res/2023/day-4-example-1.txt for day 4
+2: res/2023/day-4-example-2.txt for day 4
+'Card 1'
+'Card 2'
+end
+"
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn can_report_invalid_templates() {
+        assert_eq!(
+            render("Day {{month}}\n", &placeholders(1)),
+            Err("Unknown placeholder '{{month}}'".to_string())
+        );
+        assert_eq!(
+            render("{{#examples}}\n{{example_path}}\n", &placeholders(1)),
+            Err("{{#examples}} section is never closed".to_string())
+        );
+        assert_eq!(
+            render("{{/examples}}\n", &placeholders(1)),
+            Err("{{/examples}} doesn't close an {{#examples}} section".to_string())
+        );
+    }
+}
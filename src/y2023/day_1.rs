@@ -7,40 +7,47 @@
 //! [`ValueExtractor`]s are used to codify the different logic for the two parts, see [`part_1_extractor`] and
 //! [`part_2_extractor`].
 
+use crate::error::Error;
+use crate::solution::{Answer, Solution};
 use itertools::unfold;
 use regex::Regex;
-use std::fs;
 
 /// Describes how to find digits in a string, and how to turn those into their numeric representation
-struct ValueExtractor {
+pub struct ValueExtractor {
     pattern: Regex,
     digit_mapper: fn(&str) -> u32,
 }
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-1-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 1.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-1-input.txt").expect("Failed to read file");
-
-    println!(
-        "The sum of calibration values is {}",
-        sum_calibration_values(&contents, &part_1_extractor())
-    );
-    println!(
-        "The sum of calibration values with digit strings is {}",
-        sum_calibration_values(&contents, &part_2_extractor())
-    );
+/// - The puzzle input is expected to be at `<project_root>/res/2023/day-1-input.txt`, unless another is provided
+/// - It is expected this will be run by [`crate::runner`] when the user elects to run day 1.
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed<'a> = &'a str;
+
+    /// Lines are scanned for digits by each part, so there is nothing that can fail here
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, Error> {
+        Ok(input)
+    }
+
+    fn part_1(&self, input: &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(sum_calibration_values(input, &part_1_extractor()).into())
+    }
+
+    fn part_2(&self, input: &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(sum_calibration_values(input, &part_2_extractor()).into())
+    }
 }
 
-fn part_1_extractor() -> ValueExtractor {
+pub fn part_1_extractor() -> ValueExtractor {
     ValueExtractor {
         pattern: Regex::new(r"\d").unwrap(),
         digit_mapper: |d| d.parse().unwrap(),
     }
 }
 
-fn part_2_extractor() -> ValueExtractor {
+pub fn part_2_extractor() -> ValueExtractor {
     ValueExtractor {
         pattern: Regex::new(r"(\d|one|two|three|four|five|six|seven|eight|nine)").unwrap(),
         digit_mapper: |d| match d {
@@ -59,13 +66,13 @@ fn part_2_extractor() -> ValueExtractor {
 }
 
 /// Reduce the value extracted in each line to the sum required as the puzzle answer.
-fn sum_calibration_values(input: &String, extractor: &ValueExtractor) -> u32 {
+pub fn sum_calibration_values(input: &str, extractor: &ValueExtractor) -> u32 {
     input.lines().map(|line| parse_line(line, &extractor)).sum()
 }
 
 /// Return regex matches that might overlap
 ///
-/// ```rust
+/// ```rust,ignore
 /// let pattern = Regex::new(r"(eight|three)").unwrap();
 /// let res: Vec<&str> = overlapping_matches("eighthree", &pattern);
 /// assert_eq!(res, vec!("eight", "three"));
@@ -103,7 +110,7 @@ fn parse_line(line: &str, extractor: &ValueExtractor) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use crate::day_1::*;
+    use crate::y2023::day_1::*;
 
     #[test]
     fn can_parse_lines() {
@@ -128,6 +135,16 @@ mod tests {
         assert_eq!(parse_line("eighthree", &part_2_extractor), 83);
     }
 
+    #[test]
+    fn can_find_overlapping_matches() {
+        let pattern = Regex::new(r"(eight|three)").unwrap();
+
+        assert_eq!(
+            overlapping_matches("eighthree", &pattern),
+            vec!["eight", "three"]
+        );
+    }
+
     #[test]
     fn can_sum_calibration_values() {
         let part_1_input = "\
@@ -13,11 +13,13 @@
 //! * Part 2: [`sum_minimal_contents_powers`] splits the logic for each line between [`minimal_contents`] and
 //!   [`draw_power`]
 
-use std::fs;
+use crate::error::Error;
+use crate::parser::{parse_number, split_once, strip_prefix, ParseError};
+use crate::solution::{Answer, Solution};
 
 /// A record of the cubes shown in a single draw from a bag
 #[derive(Eq, PartialEq, Debug)]
-struct Draw {
+pub struct Draw {
     red: u8,
     green: u8,
     blue: u8,
@@ -31,7 +33,7 @@ impl Draw {
 
 /// A record of draws made with a specific combination of cubes
 #[derive(Eq, PartialEq, Debug)]
-struct Game {
+pub struct Game {
     id: u32,
     draws: Vec<Draw>,
 }
@@ -44,77 +46,79 @@ impl Game {
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-2-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 2.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-2-input.txt").expect("Failed to read file");
-    let games = parse_input(&contents);
+/// - The puzzle input is expected to be at `<project_root>/res/2023/day-2-input.txt`, unless another is provided
+/// - It is expected this will be run by [`crate::runner`] when the user elects to run day 2.
+pub struct Day2;
 
-    println!(
-        "The sum of valid game ids is {}",
-        sum_valid_game_ids(&games)
-    );
+impl Solution for Day2 {
+    type Parsed<'a> = Vec<Game>;
 
-    println!(
-        "The sum of minimal content powers is {}",
-        sum_minimal_contents_powers(&games)
-    );
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(&self, games: &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(sum_valid_game_ids(games).into())
+    }
+
+    fn part_2(&self, games: &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(sum_minimal_contents_powers(games).into())
+    }
 }
 
 /// Parse the puzzle input treating each line as a game specification
-fn parse_input(input: &String) -> Vec<Game> {
+pub fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
     input.lines().map(parse_game).collect()
 }
 
 /// Parse a line of the puzzle input as a [`Game`]
-fn parse_game(line: &str) -> Game {
-    let (id_part, draws_part) = line.split_once(": ").expect("Invalid line");
-
-    Game::new(
-        parse_id(id_part),
-        draws_part.split("; ").map(parse_draw).collect(),
-    )
+fn parse_game(line: &str) -> Result<Game, ParseError> {
+    let (id_part, draws_part) = split_once(line, ": ")?;
+
+    Ok(Game::new(
+        parse_id(id_part)?,
+        draws_part
+            .split("; ")
+            .map(parse_draw)
+            .collect::<Result<_, _>>()?,
+    ))
 }
 
 /// Parse `Game {{ id }}` as a numeric id
-fn parse_id(id_string: &str) -> u32 {
-    id_string
-        .replace("Game ", "")
-        .parse()
-        .expect(format!("Invalid game id {}", id_string).as_str())
+fn parse_id(id_string: &str) -> Result<u32, ParseError> {
+    parse_number(strip_prefix(id_string, "Game ")?)
 }
 
 /// Parse a comma separated list of drawn cubes as a [`Draw`]
-fn parse_draw(draw_str: &str) -> Draw {
+fn parse_draw(draw_str: &str) -> Result<Draw, ParseError> {
     let mut draw = Draw::new(0, 0, 0);
-    for (colour, count) in draw_str.split(", ").map(parse_cube) {
+    for cube in draw_str.split(", ") {
+        let (colour, count) = parse_cube(cube)?;
         match colour {
             "red" => draw.red = count,
             "green" => draw.green = count,
             "blue" => draw.blue = count,
-            _ => unreachable!("Invalid colour {}", colour),
+            _ => {
+                return Err(ParseError::new(
+                    colour,
+                    format!("Invalid colour '{}'", colour),
+                ))
+            }
         }
     }
 
-    draw
+    Ok(draw)
 }
 
 /// Parse e.g. `17 green` as a numeric count and the colour string
-fn parse_cube(cube_str: &str) -> (&str, u8) {
-    let (count_str, colour) = cube_str
-        .split_once(" ")
-        .expect(format!("Invalid cube {}", cube_str).as_str());
+fn parse_cube(cube_str: &str) -> Result<(&str, u8), ParseError> {
+    let (count_str, colour) = split_once(cube_str, " ")?;
 
-    return (
-        colour,
-        count_str
-            .parse()
-            .expect(format!("Invalid count {}", count_str).as_str()),
-    );
+    Ok((colour, parse_number(count_str)?))
 }
 
 /// This is the solution to part 1 - delegates validity to [`is_valid_game`]
-fn sum_valid_game_ids(games: &Vec<Game>) -> u32 {
+pub fn sum_valid_game_ids(games: &[Game]) -> u32 {
     games
         .into_iter()
         .filter(|&g| is_valid_game(g))
@@ -131,7 +135,7 @@ fn is_valid_game(game: &Game) -> bool {
 
 /// This is the solution to part 2 - delegates finding the minimal bag contents to [`minimal_contents`] and turning
 /// each bag into it's power with [`draw_power`]
-fn sum_minimal_contents_powers(games: &Vec<Game>) -> u32 {
+pub fn sum_minimal_contents_powers(games: &[Game]) -> u32 {
     games
         .into_iter()
         .map(|game| draw_power(&minimal_contents(&game)))
@@ -157,7 +161,7 @@ fn draw_power(draw: &Draw) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use crate::day_2::*;
+    use crate::y2023::day_2::*;
 
     fn example_games() -> Vec<Game> {
         vec![
@@ -186,24 +190,26 @@ mod tests {
         let games = example_games();
 
         assert_eq!(
-            parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
-            games[0]
+            parse_game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").as_ref(),
+            Ok(&games[0])
         );
         assert_eq!(
-            parse_game("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"),
-            games[1]
+            parse_game("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue").as_ref(),
+            Ok(&games[1])
         );
         assert_eq!(
-            parse_game("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"),
-            games[2]
+            parse_game("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red")
+                .as_ref(),
+            Ok(&games[2])
         );
         assert_eq!(
-            parse_game("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red"),
-            games[3]
+            parse_game("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red")
+                .as_ref(),
+            Ok(&games[3])
         );
         assert_eq!(
-            parse_game("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"),
-            games[4]
+            parse_game("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green").as_ref(),
+            Ok(&games[4])
         );
     }
 
@@ -217,7 +223,17 @@ Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
             .to_string();
 
-        assert_eq!(parse_input(&input), example_games());
+        assert_eq!(parse_input(&input), Ok(example_games()));
+    }
+
+    #[test]
+    fn can_report_invalid_games() {
+        let error = |line: &str| parse_game(line).unwrap_err().message;
+
+        assert_eq!(error("Game 1 3 blue"), "Expected ': ' in 'Game 1 3 blue'");
+        assert_eq!(error("Gane 1: 3 blue"), "Expected 'Game ', found 'Gane '");
+        assert_eq!(error("Game 1: x blue"), "Expected a number, found 'x'");
+        assert_eq!(error("Game 1: 3 purple"), "Invalid colour 'purple'");
     }
 
     #[test]
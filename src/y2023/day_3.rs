@@ -10,12 +10,14 @@
 //! * [`is_point_a_gear_symbol`] to filter that list to `*` symbols that might be gears
 //! * Then turns those that are valid into the expected list of [`Gear`]s
 
+use crate::error::Error;
+use crate::parser::ParseError;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
-use std::fs;
 
 /// Represents a part number as the position of the first digit, and the number it represents
 #[derive(Eq, PartialEq, Debug)]
-struct PartNumber {
+pub struct PartNumber {
     number: u32,
     x: usize,
     y: usize,
@@ -28,10 +30,10 @@ impl PartNumber {
 }
 
 /// A point in 2D space
-type Point = (usize, usize);
+pub type Point = (usize, usize);
 
 /// A map of 2D points to the part symbol at that point
-type SymbolLookup = HashMap<Point, char>;
+pub type SymbolLookup = HashMap<Point, char>;
 
 /// A representation of a gear by the two part numbers that make up its "gear ratio"
 ///
@@ -61,27 +63,29 @@ impl PartialEq for Gear {
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-3-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 3.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-3-input.txt").expect("Failed to read file");
-
-    let (part_numbers, symbol_lookup) = parse_grid(&contents);
-
-    println!(
-        "The sum of valid part numbers is {}",
-        sum_valid_part_numbers(&part_numbers, &symbol_lookup)
-    );
-
-    println!(
-        "The sum of gear ratios is {}",
-        sum_gear_ratios(&part_numbers, &symbol_lookup)
-    );
+/// - The puzzle input is expected to be at `<project_root>/res/2023/day-3-input.txt`, unless another is provided
+/// - It is expected this will be run by [`crate::runner`] when the user elects to run day 3.
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed<'a> = (Vec<PartNumber>, SymbolLookup);
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, Error> {
+        Ok(parse_grid(input)?)
+    }
+
+    fn part_1(&self, (part_numbers, symbol_lookup): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(sum_valid_part_numbers(part_numbers, symbol_lookup).into())
+    }
+
+    fn part_2(&self, (part_numbers, symbol_lookup): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(sum_gear_ratios(part_numbers, symbol_lookup).into())
+    }
 }
 
 /// Parse a string representing a 2D grid into a list of part numbers and a lookup table of points with character
-/// symbols
-fn parse_grid(input: &String) -> (Vec<PartNumber>, SymbolLookup) {
+/// symbols. Whitespace within the grid, or numbers too large for a `u32`, indicate a corrupted input.
+pub fn parse_grid(input: &str) -> Result<(Vec<PartNumber>, SymbolLookup), ParseError> {
     // Setup output variables to populate during parsing
     let mut parts = Vec::new();
     let mut symbols = HashMap::new();
@@ -105,7 +109,7 @@ fn parse_grid(input: &String) -> (Vec<PartNumber>, SymbolLookup) {
     }
 
     for (y, line) in input.lines().enumerate() {
-        for (x, chr) in line.chars().enumerate() {
+        for (x, (offset, chr)) in line.char_indices().enumerate() {
             // We only know we've completed a part number when we next see a non-digit character. Check for that here
             // and emit the `PartNumber`.
             if !chr.is_digit(10) {
@@ -118,7 +122,18 @@ fn parse_grid(input: &String) -> (Vec<PartNumber>, SymbolLookup) {
                 // For PartNumbers build the number digit by digit, recording the origin on the first digit seen
                 c if c.is_digit(10) => {
                     num_origin = num_origin.or(Some((x, y)));
-                    num = num * 10 + chr.to_digit(10).expect("Tested with is_digit");
+                    num = num
+                        .checked_mul(10)
+                        .and_then(|num| {
+                            num.checked_add(chr.to_digit(10).expect("Tested with is_digit"))
+                        })
+                        .ok_or_else(|| {
+                            ParseError::new(&line[offset..=offset], "Part number is too large")
+                        })?;
+                }
+                c if c.is_whitespace() => {
+                    let text = &line[offset..offset + c.len_utf8()];
+                    return Err(ParseError::new(text, "Unexpected whitespace in grid"));
                 }
                 // Anything else is an arbitrary part symbol
                 _ => {
@@ -131,11 +146,11 @@ fn parse_grid(input: &String) -> (Vec<PartNumber>, SymbolLookup) {
         build_part_number_and_reset(&mut parts, &mut num, &mut num_origin);
     }
 
-    (parts, symbols)
+    Ok((parts, symbols))
 }
 
 /// Solves part 1 - the sum of part numbers next to a symbol
-fn sum_valid_part_numbers(part_numbers: &Vec<PartNumber>, symbol_lookup: &SymbolLookup) -> u32 {
+pub fn sum_valid_part_numbers(part_numbers: &[PartNumber], symbol_lookup: &SymbolLookup) -> u32 {
     part_numbers
         .iter()
         .filter(|&part_number| has_adjacent_symbol(part_number, symbol_lookup))
@@ -173,7 +188,7 @@ fn get_adjacent_points(part_number: &PartNumber) -> Vec<Point> {
 }
 
 /// Return a list of valid gears. A gear is any `*` symbol with exactly two adjacent PartNumbers.
-fn find_gears(part_numbers: &Vec<PartNumber>, symbol_lookup: &SymbolLookup) -> Vec<Gear> {
+fn find_gears(part_numbers: &[PartNumber], symbol_lookup: &SymbolLookup) -> Vec<Gear> {
     // Since PartNumbers can have variable length it is easier to start with all the points adjacent to part numbers
     // and then filter to part number / `*` point pairs ...
     let part_nums_adjacent_to_gear_points = part_numbers
@@ -215,7 +230,7 @@ fn is_point_a_gear_symbol(point: &Point, symbol_lookup: &SymbolLookup) -> bool {
 }
 
 /// Solution to part 2 - finds all the valid gears and sums the multiplications of their "gear ratio" numbers.
-fn sum_gear_ratios(part_numbers: &Vec<PartNumber>, symbol_lookup: &SymbolLookup) -> u32 {
+pub fn sum_gear_ratios(part_numbers: &[PartNumber], symbol_lookup: &SymbolLookup) -> u32 {
     find_gears(part_numbers, symbol_lookup)
         .iter()
         .map(|Gear { part_1, part_2 }| part_1 * part_2)
@@ -224,8 +239,8 @@ fn sum_gear_ratios(part_numbers: &Vec<PartNumber>, symbol_lookup: &SymbolLookup)
 
 #[cfg(test)]
 mod tests {
-    use crate::day_3::*;
     use crate::helpers::test::assert_contains_in_any_order;
+    use crate::y2023::day_3::*;
 
     fn sample_input() -> String {
         return "\
@@ -276,13 +291,23 @@ mod tests {
 
         let expected_symbol_lookup = example_symbol_lookup();
 
-        let (parts, symbols) = parse_grid(&sample_input());
+        let (parts, symbols) = parse_grid(&sample_input()).unwrap();
 
         assert_contains_in_any_order(parts, expected_parts);
 
         assert_contains_in_any_order(symbols, expected_symbol_lookup);
     }
 
+    #[test]
+    fn can_report_invalid_grid() {
+        let error = parse_grid("467..114..\n...* ....").unwrap_err();
+        assert_eq!(error.message, "Unexpected whitespace in grid");
+        assert_eq!(error.text, " ");
+
+        let error = parse_grid("..99999999999..").unwrap_err();
+        assert_eq!(error.message, "Part number is too large");
+    }
+
     #[test]
     fn can_find_adjacent_points() {
         #[rustfmt::skip] // Positional coordinates
@@ -357,7 +382,7 @@ mod tests {
 ..3.."
             .to_string();
 
-        let (part_numbers, symbol_lookup) = parse_grid(&example_grid);
+        let (part_numbers, symbol_lookup) = parse_grid(&example_grid).unwrap();
         let expected_gears = vec![Gear::new(1, 3), Gear::new(2, 3)];
 
         assert_contains_in_any_order(find_gears(&part_numbers, &symbol_lookup), expected_gears)
@@ -1,38 +1,34 @@
 //! This is my solution for [Advent of Code - Day 4: _Scratchcards_](https://adventofcode.com/2023/day/4)
 //!
-//! [`Scratchcard`] handles parsing ([`Scratchcard::from`]), and scoring ([`Scratchcard::match_count`] and
+//! [`Scratchcard`] handles parsing ([`Scratchcard::try_from`]), and scoring ([`Scratchcard::match_count`] and
 //! [`Scratchcard::score`]).
 //!
 //! Part 1 is solved by [`sum_scores`], part 2 by [`calculate_total_cards`].
 
+use crate::error::Error;
+use crate::parser::{parse_numbers, split_once, ParseError};
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
-use std::fs;
 
 /// Represents a scratchcard (one line of input)
 #[derive(Eq, PartialEq, Debug)]
-struct Scratchcard {
+pub struct Scratchcard {
     winning_numbers: HashSet<i32>,
     numbers_you_have: HashSet<i32>,
 }
 
-impl From<&str> for Scratchcard {
-    /// Parse a string in the format `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`
-    fn from(value: &str) -> Self {
-        fn parse_set(numbers: &str) -> HashSet<i32> {
-            numbers
-                .split(" ")
-                .filter_map(|num| num.parse().ok())
-                .collect()
-        }
+impl TryFrom<&str> for Scratchcard {
+    type Error = ParseError;
 
-        let (winning_str, your_str) = value
-            .split_once(" | ")
-            .expect(format!("Invalid scratchcard {}", value).as_str());
-
-        Scratchcard {
-            winning_numbers: parse_set(winning_str),
-            numbers_you_have: parse_set(your_str),
-        }
+    /// Parse a string in the format `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (_, numbers) = split_once(value, ": ")?;
+        let (winning_str, your_str) = split_once(numbers, " | ")?;
+
+        Ok(Scratchcard {
+            winning_numbers: parse_numbers(winning_str)?.into_iter().collect(),
+            numbers_you_have: parse_numbers(your_str)?.into_iter().collect(),
+        })
     }
 }
 
@@ -61,36 +57,39 @@ impl Scratchcard {
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-4-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 4.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-4-input.txt").expect("Failed to read file");
-
-    let scratchcards = parse_input(&contents);
-
-    println!(
-        "The sum of scratchcard scores is: {}",
-        sum_scores(&scratchcards)
-    );
-    println!(
-        "The total number of scratchcards is: {}",
-        calculate_total_cards(&scratchcards)
-    );
+/// - The puzzle input is expected to be at `<project_root>/res/2023/day-4-input.txt`, unless another is provided
+/// - It is expected this will be run by [`crate::runner`] when the user elects to run day 4.
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed<'a> = Vec<Scratchcard>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(&self, scratchcards: &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(sum_scores(scratchcards).into())
+    }
+
+    fn part_2(&self, scratchcards: &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(calculate_total_cards(scratchcards)?.into())
+    }
 }
 
 /// Parse each line as a card
-fn parse_input(input: &String) -> Vec<Scratchcard> {
-    input.lines().map(Scratchcard::from).collect()
+pub fn parse_input(input: &str) -> Result<Vec<Scratchcard>, ParseError> {
+    input.lines().map(Scratchcard::try_from).collect()
 }
 
 /// Part 1 solution - calculate and sum the scores for all cards
-fn sum_scores(scratchcards: &Vec<Scratchcard>) -> i32 {
+pub fn sum_scores(scratchcards: &[Scratchcard]) -> i32 {
     scratchcards.iter().map(Scratchcard::score).sum()
 }
 
-/// Part 2 solution - each card wins a copy of the next n cards where n is the number of winning matches. This is
-/// guaranteed not to overflow the list of available cards.
-fn calculate_total_cards(scratchcards: &Vec<Scratchcard>) -> i32 {
+/// Part 2 solution - each card wins a copy of the next n cards where n is the number of winning matches. The puzzle
+/// guarantees this won't overflow the list of available cards, but a truncated input could.
+pub fn calculate_total_cards(scratchcards: &[Scratchcard]) -> Result<i32, Error> {
     // At the start there is one of each card
     let mut counts: Vec<i32> = (0..scratchcards.len()).map(|_| 1).collect();
 
@@ -99,16 +98,23 @@ fn calculate_total_cards(scratchcards: &Vec<Scratchcard>) -> i32 {
         let copies_of_current_card = counts[current_card_index];
 
         for insert_offset in 1..=scratchcard.match_count() {
-            counts[current_card_index + insert_offset] += copies_of_current_card
+            *counts
+                .get_mut(current_card_index + insert_offset)
+                .ok_or_else(|| {
+                    Error::solver(format!(
+                        "Card {} wins copies of cards beyond the end of the table",
+                        current_card_index + 1
+                    ))
+                })? += copies_of_current_card
         }
     }
 
-    counts.iter().sum()
+    Ok(counts.iter().sum())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day_4::*;
+    use crate::y2023::day_4::*;
 
     fn example_scratchcards() -> Vec<Scratchcard> {
         return vec![
@@ -150,7 +156,21 @@ Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
             .to_string();
 
-        assert_eq!(parse_input(&input), example_scratchcards())
+        assert_eq!(parse_input(&input), Ok(example_scratchcards()))
+    }
+
+    #[test]
+    fn can_report_invalid_cards() {
+        let error = |line: &str| Scratchcard::try_from(line).unwrap_err().message;
+
+        assert_eq!(
+            error("Card 1: 41 48 | 83 8x"),
+            "Expected a number, found '8x'"
+        );
+        assert_eq!(
+            error("Card 1: 41 48 83 86"),
+            "Expected ' | ' in '41 48 83 86'"
+        );
     }
 
     #[test]
@@ -175,6 +195,13 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
 
     #[test]
     fn can_calculate_total_cards() {
-        assert_eq!(calculate_total_cards(&example_scratchcards()), 30)
+        assert_eq!(calculate_total_cards(&example_scratchcards()).unwrap(), 30);
+
+        let mut truncated = example_scratchcards();
+        truncated.truncate(2);
+        assert_eq!(
+            calculate_total_cards(&truncated).unwrap_err().describe(),
+            "Card 1 wins copies of cards beyond the end of the table"
+        );
     }
 }
@@ -11,11 +11,13 @@
 //! seed ranges are turned into the minimum location by [`find_nearest_location`], using
 //! [`progress_id_ranges_to_category`] and [`progress_id_range`].
 
-use crate::day_5::Category::*;
+use crate::error::Error;
+use crate::parser::{next_line, parse_numbers, split_once, strip_prefix, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::y2023::day_5::Category::*;
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::fs;
 use std::str::FromStr;
 
 /// A range of ids to modify when applying the mapping for an AlmanacSection
@@ -38,7 +40,7 @@ impl AlmanacRange {
 
 /// A mapping from one category of ids to another. Ranges are stored sorted by starting source id
 #[derive(Eq, PartialEq, Debug)]
-struct AlmanacSection {
+pub struct AlmanacSection {
     source: Category,
     destination: Category,
     ranges: Vec<AlmanacRange>,
@@ -55,11 +57,11 @@ impl AlmanacSection {
 }
 
 /// A collection of category mappings grouped by source category
-type Almanac = HashMap<Category, AlmanacSection>;
+pub type Almanac = HashMap<Category, AlmanacSection>;
 
 /// The possible categories of id
 #[derive(Eq, PartialEq, Debug, Hash, Copy, Clone)]
-enum Category {
+pub enum Category {
     Seed,
     Soil,
     Fertilizer,
@@ -71,7 +73,7 @@ enum Category {
 }
 
 impl FromStr for Category {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -83,14 +85,14 @@ impl FromStr for Category {
             "temperature" => Ok(Temperature),
             "humidity" => Ok(Humidity),
             "location" => Ok(Location),
-            _ => Err(()),
+            _ => Err(ParseError::new(s, format!("Unknown category '{}'", s))),
         }
     }
 }
 
 /// A range of ids in a category that should be planted
 #[derive(Eq, PartialEq, Debug, Clone)]
-struct IdRange {
+pub struct IdRange {
     category: Category,
     start: i64,
     length: i64,
@@ -108,47 +110,49 @@ impl IdRange {
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-5-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 5.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-5-input.txt").expect("Failed to read file");
-
-    let (seeds, almanac) = parse_input(&contents);
-
-    println!(
-        "The nearest location id from individual seeds is: {}",
-        find_nearest_location(ids_as_single_seeds(&seeds), &almanac)
-    );
-
-    println!(
-        "The nearest location id from seed ranges is: {}",
-        find_nearest_location(ids_to_ranges(&seeds), &almanac)
-    )
+/// - The puzzle input is expected to be at `<project_root>/res/2023/day-5-input.txt`, unless another is provided
+/// - It is expected this will be run by [`crate::runner`] when the user elects to run day 5.
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed<'a> = (Vec<i64>, Almanac);
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(&self, (seeds, almanac): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(find_nearest_location(ids_as_single_seeds(seeds), almanac)?.into())
+    }
+
+    fn part_2(&self, (seeds, almanac): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(find_nearest_location(ids_to_ranges(seeds), almanac)?.into())
+    }
 }
 
 /// Split and parse the puzzle input into the list of seeds and the almanac specification.
 /// The seeds and each section are delimited by blank lines.
-fn parse_input(input: &String) -> (Vec<i64>, Almanac) {
-    let mut parts = input.split("\n\n");
+pub fn parse_input(input: &str) -> Result<(Vec<i64>, Almanac), ParseError> {
+    let (seeds_spec, almanac_spec) = split_once(input, "\n\n")?;
 
-    (parse_seeds(parts.next().unwrap()), parse_almanac(parts))
+    Ok((
+        parse_seeds(seeds_spec)?,
+        parse_almanac(almanac_spec.split("\n\n"))?,
+    ))
 }
 
-/// Parse the list of seeds to numeric ids
-fn parse_seeds(input: &str) -> Vec<i64> {
-    input
-        .split(" ")
-        .filter_map(|id| id.parse::<i64>().ok())
-        .collect()
+/// Parse the list of seeds, in the format `seeds: 79 14 55 13`, to numeric ids
+fn parse_seeds(input: &str) -> Result<Vec<i64>, ParseError> {
+    parse_numbers(strip_prefix(input, "seeds: ")?)
 }
 
 /// Each almanac section is a single header line, then one line per id mapping
-fn parse_almanac<'a>(section_specs: impl Iterator<Item = &'a str>) -> Almanac {
+fn parse_almanac<'a>(section_specs: impl Iterator<Item = &'a str>) -> Result<Almanac, ParseError> {
     let mut almanac = HashMap::new();
 
     for section_spec in section_specs {
         let mut lines = section_spec.lines();
-        let (from, to) = parse_header(lines.next().unwrap());
+        let (from, to) = parse_header(next_line(&mut lines, section_spec, "a section header")?)?;
         almanac.insert(
             from.clone(),
             AlmanacSection::new(
@@ -156,41 +160,56 @@ fn parse_almanac<'a>(section_specs: impl Iterator<Item = &'a str>) -> Almanac {
                 to,
                 lines
                     .map(parse_range)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
                     .sorted_by(|a, b| a.start.cmp(&b.start))
                     .collect(),
             ),
         );
     }
 
-    almanac
+    Ok(almanac)
 }
 
 /// Parse a header in the format `seed-to-soil map:` into source and destination categories
-fn parse_header(header_spec: &str) -> (Category, Category) {
-    let mapping_part = header_spec.replace(" map:", "");
-    let (from, to) = mapping_part.split_once("-to-").unwrap();
-    return (from.parse().unwrap(), to.parse().unwrap());
+fn parse_header(header_spec: &str) -> Result<(Category, Category), ParseError> {
+    let mapping_part = header_spec.strip_suffix(" map:").ok_or_else(|| {
+        ParseError::new(
+            header_spec,
+            format!(
+                "Expected a header like 'seed-to-soil map:', found '{}'",
+                header_spec
+            ),
+        )
+    })?;
+    let (from, to) = split_once(mapping_part, "-to-")?;
+    Ok((from.parse()?, to.parse()?))
 }
 
 /// Parse a range of id mappings, three space-separated numbers in the order `destination_start` `source_start` `length`
-fn parse_range(range_spec: &str) -> AlmanacRange {
-    let parts: Vec<i64> = range_spec
-        .split(" ")
-        .filter_map(|part| part.parse().ok())
-        .collect();
-
-    AlmanacRange::new(parts[1], parts[2], parts[0] - parts[1])
+fn parse_range(range_spec: &str) -> Result<AlmanacRange, ParseError> {
+    match parse_numbers(range_spec)?[..] {
+        [destination_start, source_start, length] => Ok(AlmanacRange::new(
+            source_start,
+            length,
+            destination_start - source_start,
+        )),
+        _ => Err(ParseError::new(
+            range_spec,
+            format!("Expected three numbers, found '{}'", range_spec),
+        )),
+    }
 }
 
 /// For part one each seed is a single id, which can be represented as a range of length 1
-fn ids_as_single_seeds(ids: &Vec<i64>) -> Vec<IdRange> {
+pub fn ids_as_single_seeds(ids: &[i64]) -> Vec<IdRange> {
     ids.into_iter()
         .map(|&start| IdRange::new(Seed, start, 1))
         .collect()
 }
 
 /// For part two each pair of numbers represents a range, in the format `start length`
-fn ids_to_ranges(ids: &Vec<i64>) -> Vec<IdRange> {
+pub fn ids_to_ranges(ids: &[i64]) -> Vec<IdRange> {
     ids.into_iter()
         .tuples()
         .map(|(&start, &length)| IdRange::new(Seed, start, length))
@@ -198,12 +217,12 @@ fn ids_to_ranges(ids: &Vec<i64>) -> Vec<IdRange> {
 }
 
 /// Apply all almanac mappings, return the start of the lowest resulting range
-fn find_nearest_location(seeds: Vec<IdRange>, almanac: &Almanac) -> i64 {
-    progress_id_ranges_to_category(seeds, Location, almanac)
+pub fn find_nearest_location(seeds: Vec<IdRange>, almanac: &Almanac) -> Result<i64, Error> {
+    progress_id_ranges_to_category(seeds, Location, almanac)?
         .iter()
         .map(|range| range.start)
         .min()
-        .unwrap()
+        .ok_or_else(|| Error::solver("There are no seeds to plant"))
 }
 
 /// Recursively advance a list of category ids until a specific category is reached
@@ -211,32 +230,38 @@ fn progress_id_ranges_to_category(
     id_ranges: Vec<IdRange>,
     category: Category,
     almanac: &Almanac,
-) -> Vec<IdRange> {
-    let current_category = id_ranges.get(0).unwrap().category;
+) -> Result<Vec<IdRange>, Error> {
+    let Some(current_category) = id_ranges.first().map(|range| range.category) else {
+        return Ok(id_ranges);
+    };
 
     if current_category == category {
-        id_ranges
+        Ok(id_ranges)
     } else {
-        progress_id_ranges_to_category(
-            id_ranges
-                .iter()
-                .flat_map(|range| progress_id_range(range, almanac))
-                .collect(),
-            category,
-            almanac,
-        )
+        let next_ranges = id_ranges
+            .iter()
+            .map(|range| progress_id_range(range, almanac))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        progress_id_ranges_to_category(next_ranges.concat(), category, almanac)
     }
 }
 
 /// Take a single range of ids to plant in one category and apply the relevant mapping from the almanac.
 /// - Where different mappings apply to different parts of the range, return a separate continuous range for each
 /// - Where a mapping is not defined, the id doesn't change, but the category still advances
-fn progress_id_range(id_range: &IdRange, almanac: &Almanac) -> Vec<IdRange> {
+/// - Fails if the almanac has no section for the range's category
+fn progress_id_range(id_range: &IdRange, almanac: &Almanac) -> Result<Vec<IdRange>, Error> {
     let mut new_id_ranges = Vec::new();
     let mut current = id_range.start;
     let id_range_end = id_range.start + id_range.length;
 
-    let section = almanac.get(&id_range.category).unwrap();
+    let section = almanac.get(&id_range.category).ok_or_else(|| {
+        Error::solver(format!(
+            "The almanac has no map from the {:?} category",
+            id_range.category
+        ))
+    })?;
 
     // ranges are already sorted, so walk through them adding mapped ranges where they overlap
     for almanac_range in &section.ranges {
@@ -284,13 +309,13 @@ fn progress_id_range(id_range: &IdRange, almanac: &Almanac) -> Vec<IdRange> {
         ))
     }
 
-    new_id_ranges
+    Ok(new_id_ranges)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day_5::*;
     use crate::helpers::test::assert_contains_in_any_order;
+    use crate::y2023::day_5::*;
 
     fn example_seeds() -> Vec<i64> {
         vec![79, 14, 55, 13]
@@ -410,12 +435,32 @@ humidity-to-location map:
 56 93 4"
             .to_string();
 
-        let (actual_seeds, actual_maps) = parse_input(&input);
+        let (actual_seeds, actual_maps) = parse_input(&input).unwrap();
 
         assert_eq!(actual_seeds, example_seeds());
         assert_contains_in_any_order(actual_maps, example_almanac());
     }
 
+    #[test]
+    fn can_report_invalid_almanacs() {
+        assert_eq!(
+            parse_header("seed-to-dirt map:").unwrap_err().message,
+            "Unknown category 'dirt'"
+        );
+        assert_eq!(
+            parse_header("seed-to-soil").unwrap_err().message,
+            "Expected a header like 'seed-to-soil map:', found 'seed-to-soil'"
+        );
+        assert_eq!(
+            parse_range("50 98").unwrap_err().message,
+            "Expected three numbers, found '50 98'"
+        );
+        assert_eq!(
+            parse_input("seeds: 79 14").unwrap_err().message,
+            "Expected '\\n\\n' in 'seeds: 79 14'"
+        );
+    }
+
     #[test]
     fn can_explode_seed_pairs() {
         let expected_ranges: Vec<IdRange> =
@@ -428,7 +473,7 @@ humidity-to-location map:
     fn can_progress_id_ranges() {
         let almanac = example_almanac();
         assert_eq!(
-            progress_id_range(&IdRange::new(Seed, 0, 100), &almanac),
+            progress_id_range(&IdRange::new(Seed, 0, 100), &almanac).unwrap(),
             vec![
                 IdRange::new(Soil, 0, 50),
                 IdRange::new(Soil, 52, 48),
@@ -436,20 +481,27 @@ humidity-to-location map:
             ]
         );
         assert_eq!(
-            progress_id_range(&IdRange::new(Seed, 97, 2), &almanac),
+            progress_id_range(&IdRange::new(Seed, 97, 2), &almanac).unwrap(),
             vec![IdRange::new(Soil, 99, 1), IdRange::new(Soil, 50, 1)]
         );
+        assert_eq!(
+            progress_id_range(&IdRange::new(Location, 1, 2), &almanac)
+                .unwrap_err()
+                .describe(),
+            "The almanac has no map from the Location category"
+        );
     }
 
     #[test]
     fn can_find_nearest_location_from_ranges() {
         assert_eq!(
-            find_nearest_location(ids_as_single_seeds(&example_seeds()), &example_almanac()),
+            find_nearest_location(ids_as_single_seeds(&example_seeds()), &example_almanac())
+                .unwrap(),
             35
         );
 
         assert_eq!(
-            find_nearest_location(ids_to_ranges(&example_seeds()), &example_almanac()),
+            find_nearest_location(ids_to_ranges(&example_seeds()), &example_almanac()).unwrap(),
             46
         );
     }
@@ -8,11 +8,13 @@
 //! the winning range of seconds to hold before releasing the boat. [`find_product_of_races`] can be used for both
 //! parts, as the single race is unchanged by `iter().product`.
 
-use std::fs;
+use crate::error::Error;
+use crate::parser::{next_line, parse_numbers, strip_prefix, ParseError};
+use crate::solution::{Answer, Solution};
 
 /// A race duration, with the distance to beat in that time
 #[derive(Eq, PartialEq, Debug)]
-struct Race {
+pub struct Race {
     duration: i64,
     distance_record: i64,
 }
@@ -28,53 +30,85 @@ impl Race {
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-6-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 6.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-6-input.txt").expect("Failed to read file");
-
-    println!(
-        "The product of the number of ways to win is: {}",
-        find_product_of_races(&parse_input(&contents, part_1_line_parser))
-    );
-
-    println!(
-        "The number of ways to win the combined race is: {}",
-        find_product_of_races(&parse_input(&contents, part_2_line_parser))
-    );
+/// - The puzzle input is expected to be at `<project_root>/res/2023/day-6-input.txt`, unless another is provided
+/// - It is expected this will be run by [`crate::runner`] when the user elects to run day 6.
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed<'a> = (Vec<Race>, Vec<Race>);
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, Error> {
+        Ok((
+            parse_input(input, part_1_line_parser)?,
+            parse_input(input, part_2_line_parser)?,
+        ))
+    }
+
+    fn part_1(&self, (races, _): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(find_product_of_races(races).into())
+    }
+
+    fn part_2(&self, (_, races): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(find_product_of_races(races).into())
+    }
 }
 
 /// Parse input from a line of durations and a line current record best times into a
 /// list of records. How to parse each line is abstracted to a `line_parser` for each part
-fn parse_input(input: &String, line_parser: fn(&str) -> Vec<i64>) -> Vec<Race> {
-    let mut lines = input.split("\n");
-    line_parser(lines.next().unwrap())
+pub fn parse_input(
+    input: &str,
+    line_parser: fn(&str) -> Result<Vec<i64>, ParseError>,
+) -> Result<Vec<Race>, ParseError> {
+    let mut lines = input.lines();
+    let durations = line_parser(strip_prefix(
+        next_line(&mut lines, input, "race times")?,
+        "Time:",
+    )?)?;
+    let distances_line = next_line(&mut lines, input, "distance records")?;
+    let distances = line_parser(strip_prefix(distances_line, "Distance:")?)?;
+
+    if durations.len() != distances.len() {
+        return Err(ParseError::new(
+            distances_line,
+            format!(
+                "Expected {} distance records, found {}",
+                durations.len(),
+                distances.len()
+            ),
+        ));
+    }
+
+    Ok(durations
         .iter()
-        .zip(line_parser(lines.next().unwrap()))
+        .zip(distances)
         .map(|(&t, d)| Race::new(t, d))
-        .collect()
+        .collect())
 }
 
 /// Parse lines as multiple numbers separated by whitespace
-fn part_1_line_parser(line: &str) -> Vec<i64> {
-    line.split(" ")
-        .filter_map(|word| word.parse().ok())
-        .collect()
+pub fn part_1_line_parser(line: &str) -> Result<Vec<i64>, ParseError> {
+    parse_numbers(line)
 }
 
-/// Parse lines as a single number each
-fn part_2_line_parser(line: &str) -> Vec<i64> {
-    let num = line
-        .chars()
-        .filter_map(|chr| chr.to_digit(10))
-        .fold(0i64, |acc, digit| acc * 10 + digit as i64);
+/// Parse lines as a single number each, ignoring the whitespace between digits
+pub fn part_2_line_parser(line: &str) -> Result<Vec<i64>, ParseError> {
+    let mut num = 0i64;
+    for (offset, chr) in line.char_indices().filter(|(_, chr)| !chr.is_whitespace()) {
+        let digit = chr.to_digit(10).ok_or_else(|| {
+            ParseError::new(
+                &line[offset..offset + chr.len_utf8()],
+                format!("Expected a digit, found '{}'", chr),
+            )
+        })?;
+        num = num * 10 + digit as i64;
+    }
 
-    return vec![num];
+    Ok(vec![num])
 }
 
 /// Convert a list of races into the size of the range of hold times, and find the product of these as the puzzle
 /// answer.
-fn find_product_of_races(races: &Vec<Race>) -> i64 {
+pub fn find_product_of_races(races: &[Race]) -> i64 {
     races.iter().map(find_count_of_winning_hold_times).product()
 }
 
@@ -100,23 +134,45 @@ fn find_count_of_winning_hold_times(race: &Race) -> i64 {
 
 #[cfg(test)]
 mod tests {
-    use crate::day_6::*;
+    use crate::y2023::day_6::*;
 
     #[test]
     fn can_parse_input_for_part_1() {
         assert_eq!(
-            parse_input(&example_input(), part_1_line_parser),
+            parse_input(&example_input(), part_1_line_parser).unwrap(),
             vec![Race::new(7, 9), Race::new(15, 40), Race::new(30, 200)]
         );
     }
     #[test]
     fn can_parse_input_for_part_2() {
         assert_eq!(
-            parse_input(&example_input(), part_2_line_parser),
+            parse_input(&example_input(), part_2_line_parser).unwrap(),
             vec![Race::new(71530, 940200)]
         );
     }
 
+    #[test]
+    fn can_report_invalid_races() {
+        assert_eq!(
+            parse_input("Time: 7 15\nDistance: 9", part_1_line_parser)
+                .unwrap_err()
+                .message,
+            "Expected 2 distance records, found 1"
+        );
+        assert_eq!(
+            parse_input("Time: 7 15", part_1_line_parser)
+                .unwrap_err()
+                .message,
+            "Unexpected end of input, expected distance records"
+        );
+        assert_eq!(
+            parse_input("Time: 7 1x\nDistance: 9 40", part_2_line_parser)
+                .unwrap_err()
+                .message,
+            "Expected a digit, found 'x'"
+        );
+    }
+
     fn example_input() -> String {
         "\
 Time:      7  15   30
@@ -135,7 +191,7 @@ Distance:  9  40  200
     #[test]
     fn can_find_product_of_winning_hold_times() {
         assert_eq!(
-            find_product_of_races(&parse_input(&example_input(), part_1_line_parser)),
+            find_product_of_races(&parse_input(&example_input(), part_1_line_parser).unwrap()),
             288
         );
     }
@@ -143,7 +199,7 @@ Distance:  9  40  200
     #[test]
     fn can_find_hold_times_for_combined_race() {
         assert_eq!(
-            find_product_of_races(&parse_input(&example_input(), part_2_line_parser)),
+            find_product_of_races(&parse_input(&example_input(), part_2_line_parser).unwrap()),
             71503
         );
     }
@@ -8,15 +8,17 @@
 //! [`total_winnings`] sorts the hands using [`Hand::cmp`], and enumerates the ranking to get the
 //! puzzle solutions.
 
-use crate::day_7::HandType::*;
+use crate::error::Error;
+use crate::parser::{parse_number, split_once, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::y2023::day_7::HandType::*;
 use itertools::Itertools;
 use std::cmp::Ordering;
-use std::fs;
 use Card::*;
 
 /// A single card.
 #[derive(Eq, PartialEq, Debug, Ord, PartialOrd, Hash)]
-enum Card {
+pub enum Card {
     Joker,
     Num(u32),
     Jack,
@@ -54,7 +56,7 @@ enum HandType {
 
 /// A hand of cards, including the list of cards in drawn order, the scoring type, and amount bid
 #[derive(Eq, PartialEq, Debug)]
-struct Hand {
+pub struct Hand {
     bid: i32,
     cards: Vec<Card>,
     hand_type: HandType,
@@ -87,24 +89,34 @@ impl PartialOrd for Hand {
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-7-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 7.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-7-input.txt").expect("Failed to read file");
-
-    println!(
-        "The total winnings with Jacks are: {}",
-        total_winnings(&parse_input(&contents, parse_cards_part_1))
-    );
-
-    println!(
-        "The total winnings with Jokers are: {}",
-        total_winnings(&parse_input(&contents, parse_cards_part_2))
-    );
+/// - The puzzle input is expected to be at `<project_root>/res/2023/day-7-input.txt`, unless another is provided
+/// - It is expected this will be run by [`crate::runner`] when the user elects to run day 7.
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed<'a> = (Vec<Hand>, Vec<Hand>);
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, Error> {
+        Ok((
+            parse_input(input, parse_cards_part_1)?,
+            parse_input(input, parse_cards_part_2)?,
+        ))
+    }
+
+    fn part_1(&self, (hands, _): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(total_winnings(hands).into())
+    }
+
+    fn part_2(&self, (_, hands): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(total_winnings(hands).into())
+    }
 }
 
 /// Parse the puzzle input
-fn parse_input(input: &String, card_parser: fn(&str) -> Vec<Card>) -> Vec<Hand> {
+pub fn parse_input(
+    input: &str,
+    card_parser: fn(&str) -> Result<Vec<Card>, ParseError>,
+) -> Result<Vec<Hand>, ParseError> {
     input
         .lines()
         .map(|line| parse_hand(line, card_parser))
@@ -112,28 +124,44 @@ fn parse_input(input: &String, card_parser: fn(&str) -> Vec<Card>) -> Vec<Hand>
 }
 
 /// Parse a single line in the format `AKQJT 123`
-fn parse_hand(line: &str, card_parser: fn(&str) -> Vec<Card>) -> Hand {
-    let (card_spec, bid_spec) = line.split_once(" ").unwrap();
-    let cards: Vec<Card> = card_parser(card_spec);
+fn parse_hand(
+    line: &str,
+    card_parser: fn(&str) -> Result<Vec<Card>, ParseError>,
+) -> Result<Hand, ParseError> {
+    let (card_spec, bid_spec) = split_once(line, " ")?;
+    let cards: Vec<Card> = card_parser(card_spec)?;
+    if cards.len() != 5 {
+        return Err(ParseError::new(
+            card_spec,
+            format!("Expected five cards, found '{}'", card_spec),
+        ));
+    }
     let hand_type = calculate_hand_type(&cards);
 
-    Hand::new(bid_spec.parse().unwrap(), cards, hand_type)
+    Ok(Hand::new(parse_number(bid_spec)?, cards, hand_type))
 }
 
 /// Use part 1 parsing of `J` meaning `Jack`
-fn parse_cards_part_1(cards_spec: &str) -> Vec<Card> {
+pub fn parse_cards_part_1(cards_spec: &str) -> Result<Vec<Card>, ParseError> {
     cards_spec
-        .chars()
-        .filter_map(|c| c.try_into().ok())
+        .char_indices()
+        .map(|(offset, c)| {
+            c.try_into().map_err(|_| {
+                ParseError::new(
+                    &cards_spec[offset..offset + c.len_utf8()],
+                    format!("Invalid card '{}'", c),
+                )
+            })
+        })
         .collect()
 }
 
 /// Use part 2 parsing of `J` meaning `Joker`
-fn parse_cards_part_2(cards_spec: &str) -> Vec<Card> {
-    parse_cards_part_1(cards_spec)
+pub fn parse_cards_part_2(cards_spec: &str) -> Result<Vec<Card>, ParseError> {
+    Ok(parse_cards_part_1(cards_spec)?
         .into_iter()
         .map(|c| if c == Jack { Joker } else { c })
-        .collect()
+        .collect())
 }
 
 /// Determine the hand rank of a list of five cards.
@@ -141,7 +169,7 @@ fn parse_cards_part_2(cards_spec: &str) -> Vec<Card> {
 /// For part 1 the scoring can be uniquely calculated from the number of unique cards and the
 /// count of the most numerous value. When part 2 introduces jokers, the count of jokers is also
 /// needed.
-fn calculate_hand_type(cards: &Vec<Card>) -> HandType {
+fn calculate_hand_type(cards: &[Card]) -> HandType {
     let groups = cards.iter().counts();
     let distinct_count = groups.len();
     let max_group = groups.values().max().unwrap();
@@ -168,7 +196,7 @@ fn calculate_hand_type(cards: &Vec<Card>) -> HandType {
 
 /// Reduce a list of cards to the puzzle solution. This is their place in the ranking when sorted
 /// weakest first multiplied by the amount bid.
-fn total_winnings(hands: &Vec<Hand>) -> i32 {
+pub fn total_winnings(hands: &[Hand]) -> i32 {
     hands
         .iter()
         .sorted()
@@ -179,7 +207,7 @@ fn total_winnings(hands: &Vec<Hand>) -> i32 {
 
 #[cfg(test)]
 mod tests {
-    use crate::day_7::*;
+    use crate::y2023::day_7::*;
     use std::cmp::Ordering::*;
 
     fn example_hands() -> Vec<Hand> {
@@ -206,27 +234,49 @@ KTJJT 220
 QQQJA 483"
             .to_string();
 
-        assert_eq!(parse_input(&input, parse_cards_part_1), example_hands());
+        assert_eq!(parse_input(&input, parse_cards_part_1), Ok(example_hands()));
+    }
+
+    #[test]
+    fn can_report_invalid_hands() {
+        let error = |line: &str| parse_hand(line, parse_cards_part_1).unwrap_err().message;
+
+        assert_eq!(error("32T3K"), "Expected ' ' in '32T3K'");
+        assert_eq!(error("32X3K 765"), "Invalid card 'X'");
+        assert_eq!(error("32T3 765"), "Expected five cards, found '32T3'");
+        assert_eq!(error("32T3K lots"), "Expected a number, found 'lots'");
     }
 
     #[test]
     fn can_determine_hand_type() {
         assert_eq!(
-            calculate_hand_type(&parse_cards_part_1("AAAAA")),
+            calculate_hand_type(&parse_cards_part_1("AAAAA").unwrap()),
             FiveOfAKind
         );
         assert_eq!(
-            calculate_hand_type(&parse_cards_part_1("AA8AA")),
+            calculate_hand_type(&parse_cards_part_1("AA8AA").unwrap()),
             FourOfAKind
         );
-        assert_eq!(calculate_hand_type(&parse_cards_part_1("23332")), FullHouse);
         assert_eq!(
-            calculate_hand_type(&parse_cards_part_1("TTT98")),
+            calculate_hand_type(&parse_cards_part_1("23332").unwrap()),
+            FullHouse
+        );
+        assert_eq!(
+            calculate_hand_type(&parse_cards_part_1("TTT98").unwrap()),
             ThreeOfAKind
         );
-        assert_eq!(calculate_hand_type(&parse_cards_part_1("23432")), TwoPair);
-        assert_eq!(calculate_hand_type(&parse_cards_part_1("A23A4")), OnePair);
-        assert_eq!(calculate_hand_type(&parse_cards_part_1("23456")), HighCard);
+        assert_eq!(
+            calculate_hand_type(&parse_cards_part_1("23432").unwrap()),
+            TwoPair
+        );
+        assert_eq!(
+            calculate_hand_type(&parse_cards_part_1("A23A4").unwrap()),
+            OnePair
+        );
+        assert_eq!(
+            calculate_hand_type(&parse_cards_part_1("23456").unwrap()),
+            HighCard
+        );
     }
 
     #[test]
@@ -247,8 +297,8 @@ QQQJA 483"
         ];
 
         for (str_a, str_b, expected) in examples {
-            let hand_a = parse_hand(&str_a, parse_cards_part_1);
-            let hand_b = parse_hand(&str_b, parse_cards_part_1);
+            let hand_a = parse_hand(&str_a, parse_cards_part_1).unwrap();
+            let hand_b = parse_hand(&str_b, parse_cards_part_1).unwrap();
 
             assert_eq!(
                 hand_a.cmp(&hand_b),
@@ -280,7 +330,7 @@ KTJJT 220
 QQQJA 483"
             .to_string();
 
-        let hands = parse_input(&input, parse_cards_part_2);
+        let hands = parse_input(&input, parse_cards_part_2).unwrap();
 
         assert_eq!(total_winnings(&hands), 5905)
     }
@@ -0,0 +1,301 @@
+//! This is my solution for [Advent of Code - Day 8: _Haunted Wasteland_](https://adventofcode.com/2023/day/8)
+//!
+//! Follows a map across a network of nodes. [`parse_input`] delegates to [`parse_instructions`],
+//! [`parse_network`], and [`parse_node`] to represent list of [`Instruction`]s and [`Network`]
+//! or [`Node`]s.
+//!
+//! [`count_steps`] counts the steps from a specific starting node to one that satisfies a given
+//! predicate. [`count_parallel_steps`] determines how long the ghosts need to cycle until they
+//! all reach a destination, assuming they are all on a regular loop through the network.
+
+use crate::error::Error;
+use crate::parser::{split_once, strip_prefix, strip_suffix, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::y2023::day_8::Instruction::{Left, Right};
+use itertools::Itertools;
+use num::Integer;
+use std::collections::HashMap;
+
+/// An instruction determining which branch to follow when moving to the next node
+#[derive(Eq, PartialEq, Debug)]
+pub enum Instruction {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Instruction {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Left),
+            'R' => Ok(Right),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A node in the network defined by the labels of the nodes you can reach next
+pub type Node<'a> = (&'a str, &'a str);
+
+/// A networks of [`Node`]s indexed by their label.
+pub type Network<'a> = HashMap<&'a str, Node<'a>>;
+
+/// The entry point for running the solutions with the 'real' puzzle input.
+///
+/// - The puzzle input is expected to be at `<project_root>/res/2023/day-8-input.txt`, unless another is provided
+/// - It is expected this will be run by [`crate::runner`] when the user elects to run day 8.
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed<'a> = (Vec<Instruction>, Network<'a>);
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(&self, (instructions, network): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(count_steps("AAA", part_1_terminal, instructions, network)?.into())
+    }
+
+    fn part_2(&self, (instructions, network): &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(count_parallel_steps(instructions, network)?.into())
+    }
+}
+
+/// Parse the input as an [`Instruction`] line followed by a [`Network`] of [`Node`]s
+pub fn parse_input(input: &str) -> Result<(Vec<Instruction>, Network<'_>), ParseError> {
+    let (instructions_spec, network_spec) = split_once(input, "\n\n")?;
+
+    Ok((
+        parse_instructions(instructions_spec)?,
+        parse_network(network_spec)?,
+    ))
+}
+
+/// Parse a line of `L` and `R` as [`Instruction`]s.
+fn parse_instructions(line: &str) -> Result<Vec<Instruction>, ParseError> {
+    line.char_indices()
+        .map(|(offset, c)| {
+            c.try_into().map_err(|_| {
+                ParseError::new(
+                    &line[offset..offset + c.len_utf8()],
+                    format!("Invalid instruction '{}', expected L or R", c),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parse each line of the spec as a labelled [`Node`] in a [`Network`].
+fn parse_network(network_spec: &str) -> Result<Network<'_>, ParseError> {
+    network_spec.lines().map(parse_node).collect()
+}
+
+/// Parse a line in the format e.g. `AAA = (BBB, CCC)` as a node labelled `AAA`, linked to `BBB`
+/// and `CCC` on the left and right respectively.
+fn parse_node(node_spec: &str) -> Result<(&str, Node<'_>), ParseError> {
+    let (label, connections) = split_once(node_spec, " = ")?;
+    let (left, right) = split_once(connections, ", ")?;
+
+    Ok((label, (strip_prefix(left, "(")?, strip_suffix(right, ")")?)))
+}
+
+/// The destination for part one is the specific node labelled `ZZZ`
+pub fn part_1_terminal(position: &str) -> bool {
+    position == "ZZZ"
+}
+
+/// Any node ending in `Z` counts as a destination for part 2
+fn part_2_terminal(position: &str) -> bool {
+    position.ends_with("Z")
+}
+
+/// Follow the the list of instructions in a cycle until a destination node is reached.
+///
+/// There are only so many combinations of node and position in the instructions. If every one could have been
+/// visited without reaching a destination, the route must be looping forever, so this fails rather than hanging.
+pub fn count_steps(
+    start: &str,
+    terminal_predicate: fn(&str) -> bool,
+    instructions: &[Instruction],
+    network: &Network,
+) -> Result<usize, Error> {
+    let mut steps = 0;
+    let mut position = start;
+    let instruction_length = instructions.len();
+
+    if instruction_length == 0 {
+        return Err(Error::solver("There are no instructions to follow"));
+    }
+
+    while !terminal_predicate(position) {
+        if steps > instruction_length * network.len() {
+            return Err(Error::solver(format!(
+                "No terminal node is reachable from {}",
+                start
+            )));
+        }
+
+        let direction = &instructions[steps % instruction_length];
+        let &(left, right) = network
+            .get(position)
+            .ok_or_else(|| Error::solver(format!("Node {} is not in the network", position)))?;
+
+        position = if *direction == Left { left } else { right };
+        steps += 1;
+    }
+
+    Ok(steps)
+}
+
+/// Given a ghost starts at each of the nodes ending in `A`, and each follows the instructions in
+/// parallel, how many steps until they are all on a terminal node at the same time.
+///
+/// The ghosts each follow a fixed cycle of steps, so this can be determined using the least
+/// common multiple of each of their cycle lengths
+pub fn count_parallel_steps(
+    instructions: &[Instruction],
+    network: &Network,
+) -> Result<usize, Error> {
+    network
+        .keys()
+        .filter(|k| k.ends_with("A"))
+        .map(|&start| count_steps(start, part_2_terminal, instructions, network))
+        .fold_ok(1, |acc, steps| steps.lcm(&acc))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::y2023::day_8::*;
+
+    fn example_networks() -> Vec<Network<'static>> {
+        vec![
+            vec![
+                ("AAA", ("BBB", "CCC")),
+                ("BBB", ("DDD", "EEE")),
+                ("CCC", ("ZZZ", "GGG")),
+                ("DDD", ("DDD", "DDD")),
+                ("EEE", ("EEE", "EEE")),
+                ("GGG", ("GGG", "GGG")),
+                ("ZZZ", ("ZZZ", "ZZZ")),
+            ]
+            .into_iter()
+            .collect(),
+            vec![
+                ("AAA", ("BBB", "BBB")),
+                ("BBB", ("AAA", "ZZZ")),
+                ("ZZZ", ("ZZZ", "ZZZ")),
+            ]
+            .into_iter()
+            .collect(),
+        ]
+    }
+
+    #[test]
+    fn can_report_invalid_nodes() {
+        let error = |line: &str| parse_node(line).unwrap_err().message;
+
+        assert_eq!(
+            error("AAA (BBB, CCC)"),
+            "Expected ' = ' in 'AAA (BBB, CCC)'"
+        );
+        assert_eq!(error("AAA = (BBB CCC)"), "Expected ', ' in '(BBB CCC)'");
+        assert_eq!(error("AAA = (BBB, CCC"), "Expected ')' at the end of 'CCC'");
+        assert_eq!(
+            parse_instructions("LRX").unwrap_err().message,
+            "Invalid instruction 'X', expected L or R"
+        );
+    }
+
+    #[test]
+    fn can_parse_input() {
+        let input_0 = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)"
+            .to_string();
+
+        let input_1 = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)"
+            .to_string();
+
+        let networks = example_networks();
+
+        let (instructions_0, network_0) = parse_input(&input_0).unwrap();
+
+        assert_eq!(instructions_0, vec![Right, Left]);
+        assert_eq!(network_0, networks[0]);
+
+        let (instructions_1, network_1) = parse_input(&input_1).unwrap();
+
+        assert_eq!(instructions_1, vec![Left, Left, Right]);
+        assert_eq!(network_1, networks[1]);
+    }
+
+    #[test]
+    fn can_count_steps() {
+        let networks = example_networks();
+
+        assert_eq!(
+            count_steps("AAA", part_1_terminal, &[Right, Left], &networks[0]).unwrap(),
+            2
+        );
+        assert_eq!(
+            count_steps("AAA", part_1_terminal, &[Left, Left, Right], &networks[1]).unwrap(),
+            6
+        );
+    }
+
+    #[test]
+    fn can_report_unreachable_terminals() {
+        let networks = example_networks();
+        let error = |instructions: &[Instruction]| {
+            count_steps("AAA", part_1_terminal, instructions, &networks[0])
+                .unwrap_err()
+                .describe()
+        };
+
+        assert_eq!(
+            error(&[Left, Left]),
+            "No terminal node is reachable from AAA"
+        );
+        assert_eq!(error(&[]), "There are no instructions to follow");
+        assert_eq!(
+            count_steps("AAA", part_1_terminal, &[Left], &Network::new())
+                .unwrap_err()
+                .describe(),
+            "Node AAA is not in the network"
+        );
+    }
+
+    #[test]
+    fn can_count_parallel_steps() {
+        let input = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)"
+            .to_string();
+
+        let (instructions, network) = parse_input(&input).unwrap();
+
+        assert_eq!(count_parallel_steps(&instructions, &network).unwrap(), 6);
+    }
+}
@@ -9,42 +9,46 @@
 //! the a sequence of sequences, each sequence in turn being generated by [`build_delta_sequence`]
 //! from the previous sequence.
 
+use crate::error::Error;
+use crate::parser::{parse_numbers, ParseError};
+use crate::solution::{Answer, Solution};
 use itertools::{iterate, Itertools};
-use std::fs;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-9-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 9.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-9-input.txt").expect("Failed to read file");
-
-    let sequences = parse_input(&contents);
-
-    println!(
-        "The sum of the forwards extrapolated numbers is: {}",
-        analyse_sequences(&sequences, extrapolate_sequence_forwards)
-    );
-
-    println!(
-        "The sum of the backwards extrapolated numbers is: {}",
-        analyse_sequences(&sequences, extrapolate_sequence_backwards)
-    );
+/// - The puzzle input is expected to be at `<project_root>/res/2023/day-9-input.txt`, unless another is provided
+/// - It is expected this will be run by [`crate::runner`] when the user elects to run day 9.
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Parsed<'a> = Vec<Vec<i64>>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_1(&self, sequences: &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(analyse_sequences(sequences, extrapolate_sequence_forwards).into())
+    }
+
+    fn part_2(&self, sequences: &Self::Parsed<'_>) -> Result<Answer, Error> {
+        Ok(analyse_sequences(sequences, extrapolate_sequence_backwards).into())
+    }
 }
 
 /// Parse each line as a sequence of integers
-fn parse_input(input: &String) -> Vec<Vec<i64>> {
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
     input.lines().map(parse_line).collect()
 }
 
 /// Parse a line as a space separated list of integers
-fn parse_line(line: &str) -> Vec<i64> {
-    line.split(" ").filter_map(|num| num.parse().ok()).collect()
+fn parse_line(line: &str) -> Result<Vec<i64>, ParseError> {
+    parse_numbers(line)
 }
 
 /// Unwrap the sequence of delta sequences to extrapolate the next value in the original sequence.
 /// This is equivalent to the sum of the last value in each of the delta sequences
-fn extrapolate_sequence_forwards(sequence: &Vec<i64>) -> i64 {
+pub fn extrapolate_sequence_forwards(sequence: &[i64]) -> i64 {
     let sequences: Vec<Vec<i64>> = build_delta_sequences(sequence);
 
     sequences
@@ -56,7 +60,7 @@ fn extrapolate_sequence_forwards(sequence: &Vec<i64>) -> i64 {
 
 /// Unwrap the sequence of delta sequences to extrapolate the previous value in the original
 /// sequence. This is equivalent recursively subtracting the first value in the sequence.
-fn extrapolate_sequence_backwards(sequence: &Vec<i64>) -> i64 {
+pub fn extrapolate_sequence_backwards(sequence: &[i64]) -> i64 {
     let sequences: Vec<Vec<i64>> = build_delta_sequences(sequence);
 
     sequences
@@ -68,14 +72,14 @@ fn extrapolate_sequence_backwards(sequence: &Vec<i64>) -> i64 {
 
 /// Given a list of integers, return the sequence of sequences generated by recursively calling
 /// [`build_delta_sequence`] on the previous sequence until a sequence of `0`s is generated
-fn build_delta_sequences(sequence: &Vec<i64>) -> Vec<Vec<i64>> {
-    iterate(sequence.clone(), build_delta_sequence)
+fn build_delta_sequences(sequence: &[i64]) -> Vec<Vec<i64>> {
+    iterate(sequence.to_vec(), |sequence| build_delta_sequence(sequence))
         .take_while(|seq| seq.iter().any(|&v| v != 0))
         .collect()
 }
 
 /// Generate a sequence of the difference between each consecutive pair of numbers
-fn build_delta_sequence(sequence: &Vec<i64>) -> Vec<i64> {
+fn build_delta_sequence(sequence: &[i64]) -> Vec<i64> {
     sequence
         .into_iter()
         .tuple_windows()
@@ -84,16 +88,16 @@ fn build_delta_sequence(sequence: &Vec<i64>) -> Vec<i64> {
 }
 
 /// Extrapolate each sequence in the list, and sum the extrapolated values
-fn analyse_sequences(
-    sequences: &Vec<Vec<i64>>,
-    extrapolator: fn(sequence: &Vec<i64>) -> i64,
-) -> i64 {
-    sequences.iter().map(extrapolator).sum()
+pub fn analyse_sequences(sequences: &[Vec<i64>], extrapolator: fn(sequence: &[i64]) -> i64) -> i64 {
+    sequences
+        .iter()
+        .map(|sequence| extrapolator(sequence))
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day_9::*;
+    use crate::y2023::day_9::*;
 
     fn example_sequences() -> Vec<Vec<i64>> {
         vec![
@@ -111,14 +115,18 @@ mod tests {
 10 13 16 21 30 45"
             .to_string();
 
-        assert_eq!(parse_input(&input), example_sequences());
+        assert_eq!(parse_input(&input), Ok(example_sequences()));
+        assert_eq!(
+            parse_line("0 3 6 9 l2").unwrap_err().message,
+            "Expected a number, found 'l2'"
+        );
     }
 
     #[test]
     fn can_extrapolate_sequences_forwards() {
         let results: Vec<i64> = example_sequences()
             .iter()
-            .map(extrapolate_sequence_forwards)
+            .map(|sequence| extrapolate_sequence_forwards(sequence))
             .collect();
 
         assert_eq!(results, vec![18, 28, 68])
@@ -128,7 +136,7 @@ mod tests {
     fn can_extrapolate_sequences_backwards() {
         let results: Vec<i64> = example_sequences()
             .iter()
-            .map(extrapolate_sequence_backwards)
+            .map(|sequence| extrapolate_sequence_backwards(sequence))
             .collect();
 
         assert_eq!(results, vec![-3, 0, 5])
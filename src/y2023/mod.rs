@@ -0,0 +1,28 @@
+//! My solutions for [Advent of Code 2023](https://adventofcode.com/2023). Each day is a public module, e.g.
+//! [`day_5`], and its [`crate::solution::Solution`] is registered in [`SOLUTIONS`].
+
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+use crate::solution::Registry;
+
+/// Each day's [`crate::solution::Solution`], keyed by day. Kept one per line so that bootstrapping can add to it.
+#[rustfmt::skip]
+pub static SOLUTIONS: &Registry = &[
+    (1, &day_1::Day1),
+    (2, &day_2::Day2),
+    (3, &day_3::Day3),
+    (4, &day_4::Day4),
+    (5, &day_5::Day5),
+    (6, &day_6::Day6),
+    (7, &day_7::Day7),
+    (8, &day_8::Day8),
+    (9, &day_9::Day9),
+];
@@ -0,0 +1,326 @@
+//! Bootstraps days into a temporary copy of the project layout, using fixtures in place of the website
+
+mod common;
+
+use advent_of_code_2023::bootstrap_day::{bootstrap_day, BootstrapOptions};
+use advent_of_code_2023::client::CachedClient;
+use common::{fixtures, CountingClient, TempProject, LIB_SOURCE, MODULE_SOURCE};
+use std::fs;
+
+#[test]
+fn can_bootstrap_day() {
+    let project = TempProject::new("bootstrap");
+
+    bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2023,
+        10,
+        &BootstrapOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        project.read("res/2023/day-10-input.txt"),
+        "0 3 6 9 12 15\n1 3 6 10 15 21\n"
+    );
+
+    let solution = project.read("src/y2023/day_10.rs");
+    assert!(solution.starts_with(
+        "//! This is my solution for [Advent of Code - Day 10: _Pipe Maze_](https://adventofcode.com/2023/day/10)"
+    ));
+    assert!(solution.contains("impl Solution for Day10 {"));
+    assert!(solution.contains("        \"res/2023/day-10-example-1.txt\",\n"));
+    assert_eq!(
+        project.read("res/2023/day-10-example-1.txt"),
+        ".....\n.S-7.\n.|.|.\n.L-J.\n.....\n"
+    );
+
+    let post = project.read("pubs/blog/2023/day_10.md");
+    assert!(post.contains("day: 10\n"));
+    assert!(post.contains("header: 'Day 10: Pipe Maze'\n"));
+
+    let module = project.read("src/y2023/mod.rs");
+    assert!(module.contains("pub mod day_9;\npub mod day_10;\n"));
+    assert!(module.contains("    (9, &day_9::Day9),\n    (10, &day_10::Day10),\n"));
+    assert_eq!(project.read("src/lib.rs"), LIB_SOURCE);
+}
+
+#[test]
+fn can_report_failed_fetch() {
+    let project = TempProject::new("failed-fetch");
+
+    let error = bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2023,
+        11,
+        &BootstrapOptions::default(),
+    )
+    .unwrap_err();
+
+    assert!(error
+        .describe()
+        .starts_with("Failed to fetch the input for day 11\n  caused by: No fixture at "));
+    assert!(!project.root.join("src/y2023/day_11.rs").exists());
+    assert_eq!(project.read("src/y2023/mod.rs"), MODULE_SOURCE);
+}
+
+#[test]
+fn can_skip_existing_files() {
+    let project = TempProject::new("skip-existing");
+    fs::write(
+        project.root.join("src/y2023/day_10.rs"),
+        "// My finished solution",
+    )
+    .unwrap();
+
+    bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2023,
+        10,
+        &BootstrapOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        project.read("src/y2023/day_10.rs"),
+        "// My finished solution"
+    );
+    assert!(project.root.join("res/2023/day-10-input.txt").exists());
+
+    bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2023,
+        10,
+        &BootstrapOptions {
+            force: true,
+            ..BootstrapOptions::default()
+        },
+    )
+    .unwrap();
+
+    assert!(project
+        .read("src/y2023/day_10.rs")
+        .contains("impl Solution for Day10 {"));
+}
+
+#[test]
+fn can_cache_inputs() {
+    let project = TempProject::new("cache");
+    let cache_dir = project.root.join("res/cache");
+    let client = CachedClient::new(CountingClient::new(), &cache_dir);
+
+    bootstrap_day(
+        &client,
+        &project.root,
+        2023,
+        10,
+        &BootstrapOptions::default(),
+    )
+    .unwrap();
+    bootstrap_day(
+        &client,
+        &project.root,
+        2023,
+        10,
+        &BootstrapOptions {
+            force: true,
+            ..BootstrapOptions::default()
+        },
+    )
+    .unwrap();
+    fs::remove_file(project.root.join("res/2023/day-10-input.txt")).unwrap();
+    bootstrap_day(
+        &client,
+        &project.root,
+        2023,
+        10,
+        &BootstrapOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(client.inner().fetches.get(), 1);
+    assert_eq!(
+        project.read("res/2023/day-10-input.txt"),
+        project.read("res/cache/2023/day-10-input.txt")
+    );
+    assert!(project
+        .read("res/cache/fetched.txt")
+        .starts_with("2023 10 "));
+}
+
+#[test]
+fn can_bootstrap_new_year() {
+    let project = TempProject::new("new-year");
+    project.write("res/2024/day-1-input.txt", "3   4\n4   3\n");
+
+    bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2024,
+        1,
+        &BootstrapOptions::default(),
+    )
+    .unwrap();
+
+    let solution = project.read("src/y2024/day_1.rs");
+    assert!(solution.contains("(https://adventofcode.com/2024/day/1)"));
+    assert!(solution.contains("`<project_root>/res/2024/day-1-input.txt`"));
+    assert!(project
+        .read("pubs/blog/2024/day_1.md")
+        .contains("year: 2024\n"));
+
+    let module = project.read("src/y2024/mod.rs");
+    assert!(module.contains("pub mod day_1;\n"));
+    assert!(module.contains("    (1, &day_1::Day1),\n"));
+
+    let lib = project.read("src/lib.rs");
+    assert!(lib.contains("pub mod y2023;\npub mod y2024;\n"));
+    assert!(lib.contains("    (2023, y2023::SOLUTIONS),\n    (2024, y2024::SOLUTIONS),\n"));
+}
+
+#[test]
+fn can_bootstrap_into_collapsed_year_module() {
+    let project = TempProject::new("collapsed-year");
+    project.write("res/2024/day-1-input.txt", "3   4\n4   3\n");
+    project.write("res/2024/day-2-input.txt", "7 6 4 2 1\n");
+    bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2024,
+        1,
+        &BootstrapOptions::default(),
+    )
+    .unwrap();
+
+    // As `cargo fmt` leaves a single day's registry, if the module predates it being marked `#[rustfmt::skip]`
+    let module = project.read("src/y2024/mod.rs").replace(
+        "#[rustfmt::skip]\npub static SOLUTIONS: &Registry = &[\n    (1, &day_1::Day1),\n];",
+        "pub static SOLUTIONS: &Registry = &[(1, &day_1::Day1)];",
+    );
+    assert!(module.contains("&[(1, &day_1::Day1)];"));
+    project.write("src/y2024/mod.rs", &module);
+
+    bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2024,
+        2,
+        &BootstrapOptions::default(),
+    )
+    .unwrap();
+
+    let module = project.read("src/y2024/mod.rs");
+    assert!(module.contains("pub mod day_1;\npub mod day_2;\n"));
+    assert!(module.contains("&[\n    (1, &day_1::Day1),\n    (2, &day_2::Day2),\n];\n"));
+}
+
+#[test]
+fn can_use_custom_templates() {
+    let project = TempProject::new("custom-templates");
+    project.write(
+        "templates/day.rs.tmpl",
+        "//! {{year}} day {{day}}: {{title}}\n// {{example_path}}\n",
+    );
+    project.write("templates/post.md.tmpl", "# {{title}}\n");
+
+    bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2023,
+        10,
+        &BootstrapOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        project.read("src/y2023/day_10.rs"),
+        "//! 2023 day 10: Pipe Maze\n// res/2023/day-10-example-1.txt\n"
+    );
+    assert_eq!(project.read("pubs/blog/2023/day_10.md"), "# Pipe Maze\n");
+}
+
+#[test]
+fn can_report_invalid_templates() {
+    let project = TempProject::new("invalid-template");
+    project.write("templates/day.rs.tmpl", "//! Day {{number}}\n");
+
+    let error = bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2023,
+        10,
+        &BootstrapOptions::default(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error.describe(),
+        "Failed to render templates/day.rs.tmpl: Unknown placeholder '{{number}}'"
+    );
+    assert!(!project.root.join("src/y2023/day_10.rs").exists());
+}
+
+#[test]
+fn can_dry_run() {
+    let project = TempProject::new("dry-run");
+    let cache_dir = project.root.join("res/cache");
+    let client = CachedClient::new(CountingClient::new(), &cache_dir);
+    let dry_run = BootstrapOptions {
+        dry_run: true,
+        ..BootstrapOptions::default()
+    };
+
+    bootstrap_day(&client, &project.root, 2023, 10, &dry_run).unwrap();
+
+    assert_eq!(client.inner().fetches.get(), 0);
+    assert_eq!(client.inner().puzzle_fetches.get(), 0);
+    assert!(!project.root.join("res").exists());
+    assert!(!project.root.join("src/y2023/day_10.rs").exists());
+    assert!(!project.root.join("pubs").exists());
+    assert_eq!(project.read("src/y2023/mod.rs"), MODULE_SOURCE);
+
+    // An input that has already been cached is used, but still not written
+    project.write("res/cache/2023/day-10-input.txt", "0 3 6 9 12 15\n");
+    project.write("res/cache/fetched.txt", "2023 10 1701388800\n");
+    bootstrap_day(&client, &project.root, 2023, 10, &dry_run).unwrap();
+
+    assert_eq!(client.inner().fetches.get(), 0);
+    assert!(!project.root.join("res/2023").exists());
+    assert_eq!(
+        project.read("res/cache/fetched.txt"),
+        "2023 10 1701388800\n"
+    );
+}
+
+#[test]
+fn can_roll_back_failed_writes() {
+    let project = TempProject::new("roll-back");
+    project.write("src/y2023/day_10.rs", "// My finished solution");
+    // A directory where the blog post should be can't be overwritten, so bootstrapping fails after the input,
+    // example, and solution have been written
+    fs::create_dir_all(project.root.join("pubs/blog/2023/day_10.md")).unwrap();
+
+    let error = bootstrap_day(
+        &fixtures(),
+        &project.root,
+        2023,
+        10,
+        &BootstrapOptions {
+            force: true,
+            ..BootstrapOptions::default()
+        },
+    )
+    .unwrap_err();
+
+    assert!(error.describe().starts_with("Failed to read "));
+    assert!(!project.root.join("res").exists());
+    assert_eq!(
+        project.read("src/y2023/day_10.rs"),
+        "// My finished solution"
+    );
+    assert_eq!(project.read("src/y2023/mod.rs"), MODULE_SOURCE);
+}
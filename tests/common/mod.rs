@@ -0,0 +1,104 @@
+//! Helpers shared by the integration tests. Not every test uses all of them.
+#![allow(dead_code)]
+
+use advent_of_code_2023::client::{AocClient, FixtureClient};
+use advent_of_code_2023::error::Result;
+use advent_of_code_2023::solution::Answer;
+use std::cell::Cell;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const LIB_SOURCE: &str = "\
+pub mod solution;
+pub mod y2023;
+
+pub static YEARS: &Years = &[
+    (2023, y2023::SOLUTIONS),
+];
+";
+
+pub const MODULE_SOURCE: &str = "\
+pub mod day_1;
+pub mod day_9;
+
+#[rustfmt::skip]
+pub static SOLUTIONS: &Registry = &[
+    (1, &day_1::Day1),
+    (9, &day_9::Day9),
+];
+";
+
+/// A scratch project root containing just `src/lib.rs` and `src/y2023/mod.rs`, removed when dropped
+pub struct TempProject {
+    pub root: PathBuf,
+}
+
+impl TempProject {
+    pub fn new(name: &str) -> TempProject {
+        let root = env::temp_dir().join(format!("aoc-2023-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/y2023")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB_SOURCE).unwrap();
+        fs::write(root.join("src/y2023/mod.rs"), MODULE_SOURCE).unwrap();
+
+        TempProject { root }
+    }
+
+    pub fn write(&self, filename: &str, contents: &str) {
+        let path = self.root.join(filename);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    pub fn read(&self, filename: &str) -> String {
+        fs::read_to_string(self.root.join(filename))
+            .unwrap_or_else(|_| panic!("{} was not written", filename))
+    }
+}
+
+impl Drop for TempProject {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+pub fn fixtures() -> FixtureClient {
+    FixtureClient::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+}
+
+/// Serves requests from the fixtures, counting how many of each are made
+pub struct CountingClient {
+    fixtures: FixtureClient,
+    pub fetches: Cell<usize>,
+    pub puzzle_fetches: Cell<usize>,
+    pub submissions: Cell<usize>,
+}
+
+impl CountingClient {
+    pub fn new() -> CountingClient {
+        CountingClient {
+            fixtures: fixtures(),
+            fetches: Cell::new(0),
+            puzzle_fetches: Cell::new(0),
+            submissions: Cell::new(0),
+        }
+    }
+}
+
+impl AocClient for CountingClient {
+    fn fetch_input(&self, year: u16, day: u8) -> Result<String> {
+        self.fetches.set(self.fetches.get() + 1);
+        self.fixtures.fetch_input(year, day)
+    }
+
+    fn fetch_puzzle(&self, year: u16, day: u8) -> Result<String> {
+        self.puzzle_fetches.set(self.puzzle_fetches.get() + 1);
+        self.fixtures.fetch_puzzle(year, day)
+    }
+
+    fn submit_answer(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Result<String> {
+        self.submissions.set(self.submissions.get() + 1);
+        self.fixtures.submit_answer(year, day, part, answer)
+    }
+}
@@ -0,0 +1,54 @@
+//! Moves files from the layout used before solutions were grouped by year, in a temporary copy of the project layout
+
+mod common;
+
+use advent_of_code_2023::client::{AocClient, CachedClient};
+use advent_of_code_2023::migrate::migrate_legacy_layout;
+use common::{CountingClient, TempProject};
+
+#[test]
+fn can_migrate_legacy_layout() {
+    let project = TempProject::new("migrate");
+    project.write("res/day-1-input.txt", "1abc2\n");
+    project.write("res/day-1-example-1.txt", "pqr3stu8vwx\n");
+    project.write("res/day-1-submissions.txt", "1 correct 142\n");
+    project.write("res/day-2/alice.txt", "Game 1: 3 blue\n");
+    project.write("res/answers.txt", "1 142 281\n");
+    project.write("res/bench-baseline.txt", "1 parse 10\n");
+    project.write("res/session_cookie.txt", "53616c7465645f5f\n");
+    project.write("res/cache/day-9-input.txt", "0 3 6 9 12 15\n");
+    project.write("res/cache/fetched.txt", "9 1701406800\n");
+    // Already migrated, so the legacy copy is left alone rather than replacing it
+    project.write("res/2023/day-3-input.txt", "467..114..\n");
+    project.write("res/day-3-input.txt", "old\n");
+
+    migrate_legacy_layout(&project.root).unwrap();
+
+    assert_eq!(project.read("res/2023/day-1-input.txt"), "1abc2\n");
+    assert_eq!(
+        project.read("res/2023/day-1-example-1.txt"),
+        "pqr3stu8vwx\n"
+    );
+    assert_eq!(
+        project.read("res/2023/day-1-submissions.txt"),
+        "1 correct 142\n"
+    );
+    assert_eq!(project.read("res/2023/day-2/alice.txt"), "Game 1: 3 blue\n");
+    assert_eq!(project.read("res/2023/answers.txt"), "1 142 281\n");
+    assert_eq!(project.read("res/2023/bench-baseline.txt"), "1 parse 10\n");
+    assert!(!project.root.join("res/day-1-input.txt").exists());
+    assert!(!project.root.join("res/answers.txt").exists());
+
+    assert_eq!(project.read("res/session_cookie.txt"), "53616c7465645f5f\n");
+    assert_eq!(project.read("res/2023/day-3-input.txt"), "467..114..\n");
+    assert_eq!(project.read("res/day-3-input.txt"), "old\n");
+
+    // The legacy cache entry is still used after migrating, rather than fetching the input again
+    let client = CachedClient::new(CountingClient::new(), project.root.join("res/cache"));
+    assert_eq!(client.fetch_input(2023, 9).unwrap(), "0 3 6 9 12 15\n");
+    assert_eq!(client.inner().fetches.get(), 0);
+
+    // Running it again has nothing left to move
+    migrate_legacy_layout(&project.root).unwrap();
+    assert_eq!(project.read("res/day-3-input.txt"), "old\n");
+}
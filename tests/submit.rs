@@ -0,0 +1,101 @@
+//! Submits answers from a temporary copy of the project layout, using fixtures in place of the website
+
+mod common;
+
+use advent_of_code_2023::solution::Answer;
+use advent_of_code_2023::submit::{record_submission, submit_day, Outcome, Submission};
+use advent_of_code_2023::y2023::day_6::Day6;
+use advent_of_code_2023::y2023::day_9::Day9;
+use common::{CountingClient, TempProject};
+
+#[test]
+fn can_record_wrong_answers() {
+    let project = TempProject::new("submit-wrong");
+    project.write(
+        "res/2023/day-9-input.txt",
+        "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n",
+    );
+    let client = CountingClient::new();
+
+    let outcome = submit_day(&client, &project.root, 2023, 9, &Day9, 1).unwrap();
+
+    assert_eq!(outcome, Outcome::TooHigh);
+    assert_eq!(
+        project.read("res/2023/day-9-submissions.txt"),
+        "1 too-high 114\n"
+    );
+
+    let outcome = submit_day(&client, &project.root, 2023, 9, &Day9, 1).unwrap();
+
+    assert_eq!(outcome, Outcome::TooHigh);
+    assert_eq!(client.submissions.get(), 1);
+    assert!(!project.root.join("res/2023/answers.txt").exists());
+}
+
+#[test]
+fn can_record_correct_answers() {
+    let project = TempProject::new("submit-correct");
+    project.write(
+        "res/2023/day-6-input.txt",
+        "Time:      7  15   30\nDistance:  9  40  200\n",
+    );
+    let client = CountingClient::new();
+
+    let outcome = submit_day(&client, &project.root, 2023, 6, &Day6, 1).unwrap();
+
+    assert_eq!(outcome, Outcome::Correct);
+    assert_eq!(
+        project.read("res/2023/day-6-submissions.txt"),
+        "1 correct 288\n"
+    );
+    assert_eq!(project.read("res/2023/answers.txt"), "6 1 288\n");
+}
+
+#[test]
+fn can_record_attempts_for_a_new_year() {
+    let project = TempProject::new("submit-attempt");
+    let submission = Submission {
+        part: 1,
+        outcome: Outcome::TooLow,
+        answer: Answer::Integer(11),
+    };
+
+    record_submission(
+        &project.root.join("res/2024/day-1-submissions.txt"),
+        &submission,
+    )
+    .unwrap();
+
+    assert_eq!(
+        project.read("res/2024/day-1-submissions.txt"),
+        "1 too-low 11\n"
+    );
+}
+
+#[test]
+fn can_report_missing_input() {
+    let project = TempProject::new("submit-missing");
+
+    let error = submit_day(&CountingClient::new(), &project.root, 2023, 9, &Day9, 1).unwrap_err();
+
+    assert!(error.describe().starts_with("Could not read "));
+}
+
+#[test]
+fn can_refuse_answers_known_to_be_wrong() {
+    let project = TempProject::new("submit-known-wrong");
+    project.write(
+        "res/2023/day-9-input.txt",
+        "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n",
+    );
+    project.write("res/2023/day-9-submissions.txt", "1 too-high 100\n");
+    let client = CountingClient::new();
+
+    let error = submit_day(&client, &project.root, 2023, 9, &Day9, 1).unwrap_err();
+
+    assert_eq!(
+        error.describe(),
+        "Not submitting 114 for day 9 part 1, 114 is higher than 100, which was too high"
+    );
+    assert_eq!(client.submissions.get(), 0);
+}